@@ -29,19 +29,32 @@ impl From<RuntimeError> for LoxError {
 
 impl Display for LoxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line_number = match self {
+            Self::LexerError(err) => err.line_number,
+            Self::ParseError(err) => err.token.line_number,
+            Self::RuntimeError(err) => err.line_number,
+        };
+        match self {
+            Self::RuntimeError(RuntimeError {
+                module: Some(module),
+                ..
+            }) => write!(f, "[line {} in \"{}\"] ", line_number, module)?,
+            _ => write!(f, "[line {}] ", line_number)?,
+        }
         writeln!(
             f,
-            "[line {}] Error: {}",
-            match self {
-                Self::LexerError(err) => err.line_number,
-                Self::ParseError(err) => err.token.line_number,
-                Self::RuntimeError(err) => err.line_number,
-            },
+            "Error: {}",
             match self {
                 Self::LexerError(err) => err.error_type.to_string(),
                 Self::ParseError(err) => err.message.to_string(),
                 Self::RuntimeError(err) => err.message.to_string(),
             }
-        )
+        )?;
+
+        if let Self::RuntimeError(err) = self {
+            write!(f, "{}", err.trace)?;
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,79 @@
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+// Each interpreter owns its interner, and every identifier and string literal it lexes is shared
+// through it. Two symbols from the same interner are equal exactly when they point at the same
+// string, so comparing and hashing them never looks at the text.
+#[derive(Clone)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).cast::<u8>().hash(state);
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Default)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, string: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(string) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(string);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    pub fn symbol(&mut self, name: &str) -> Symbol {
+        Symbol(self.intern(name))
+    }
+
+    // Forgets strings that nothing but the table refers to any more, so that running many
+    // different scripts doesn't grow it without bound
+    pub fn reclaim(&mut self) {
+        self.strings.retain(|string| Arc::strong_count(string) > 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
@@ -1,57 +1,140 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::lexer::Token;
+use crate::{
+    interner::Symbol,
+    lexer::{Token, TokenType},
+};
 
-use super::{value::Value, RuntimeError, RuntimeResult};
+use super::{heap, value::Value, RuntimeError, RuntimeResult};
+
+#[derive(Clone)]
+struct Binding {
+    value: Value,
+    mutable: bool,
+}
 
 #[derive(Default, Clone)]
 pub struct Environment {
     pub enclosing: Option<Rc<RefCell<Environment>>>,
-    values: HashMap<String, Value>,
+    values: HashMap<Symbol, Binding>,
 }
 
 impl Environment {
     pub fn new(enclosing: Option<Rc<RefCell<Environment>>>) -> Rc<RefCell<Environment>> {
-        Rc::new(RefCell::new(Environment {
+        let environment = Rc::new(RefCell::new(Environment {
             values: HashMap::new(),
             enclosing,
-        }))
+        }));
+        heap::register_environment(&environment);
+        environment
     }
 
-    pub fn define(&mut self, name: &str, value: Value) {
-        self.values.insert(name.to_string(), value);
+    pub(super) fn values(&self) -> impl Iterator<Item = &Value> {
+        self.values.values().map(|binding| &binding.value)
     }
 
-    pub fn get(&self, token: &Token) -> RuntimeResult<Value> {
-        let name = &token.token_type.lexeme();
+    pub fn define(&mut self, name: Symbol, value: Value) {
+        self.values.insert(
+            name,
+            Binding {
+                value,
+                mutable: true,
+            },
+        );
+    }
+
+    pub fn define_constant(&mut self, name: Symbol, value: Value) {
+        self.values.insert(
+            name,
+            Binding {
+                value,
+                mutable: false,
+            },
+        );
+    }
 
-        if let Some(value) = self.values.get(name) {
-            return Ok(value.clone());
+    pub fn declare(&mut self, name: Symbol, value: Value, line_number: usize) -> RuntimeResult<()> {
+        self.check_redeclaration(&name, line_number)?;
+        self.define(name, value);
+        Ok(())
+    }
+
+    pub fn declare_constant(
+        &mut self,
+        name: Symbol,
+        value: Value,
+        line_number: usize,
+    ) -> RuntimeResult<()> {
+        self.check_redeclaration(&name, line_number)?;
+        self.define_constant(name, value);
+        Ok(())
+    }
+
+    // A declaration may replace a var in the same scope, but never a constant
+    fn check_redeclaration(&self, name: &Symbol, line_number: usize) -> RuntimeResult<()> {
+        match self.values.get(name) {
+            Some(binding) if !binding.mutable => Err(RuntimeError::new(
+                line_number,
+                format!("Cannot redeclare constant \"{}\".", name).as_str(),
+            )),
+            _ => Ok(()),
         }
+    }
 
-        if let Some(ref enclosing) = self.enclosing {
-            return enclosing.borrow().get(token);
+    pub fn get(&self, token: &Token) -> RuntimeResult<Value> {
+        if let TokenType::Ident(name) = &token.token_type {
+            if let Some(value) = self.lookup(name) {
+                return Ok(value);
+            }
         }
 
-        Err(RuntimeError::new(
-            token.line_number,
-            format!("Undefined variable \"{}\".", name).as_str(),
-        ))
+        Err(Self::undefined(token))
+    }
+
+    pub fn lookup(&self, name: &Symbol) -> Option<Value> {
+        if let Some(binding) = self.values.get(name) {
+            return Some(binding.value.clone());
+        }
+
+        self.enclosing
+            .as_ref()
+            .and_then(|enclosing| enclosing.borrow().lookup(name))
     }
 
-    pub fn assign(&mut self, name: &Token, value: Value) -> RuntimeResult<()> {
-        if let Some(old_value) = self.values.get_mut(&name.token_type.lexeme()) {
-            *old_value = value;
+    // Only looks in this scope, which is where a module's exports live
+    pub fn is_constant(&self, name: &Symbol) -> bool {
+        self.values
+            .get(name)
+            .is_some_and(|binding| !binding.mutable)
+    }
+
+    pub fn assign(&mut self, token: &Token, value: Value) -> RuntimeResult<()> {
+        let TokenType::Ident(name) = &token.token_type else {
+            return Err(Self::undefined(token));
+        };
+
+        if let Some(binding) = self.values.get_mut(name) {
+            if !binding.mutable {
+                return Err(RuntimeError::new(
+                    token.line_number,
+                    format!("Cannot assign to constant \"{}\".", name).as_str(),
+                ));
+            }
+            binding.value = value;
             return Ok(());
         }
 
         if let Some(ref enclosing) = self.enclosing {
-            return enclosing.borrow_mut().assign(name, value);
+            return enclosing.borrow_mut().assign(token, value);
         }
 
-        Err(RuntimeError::new(
-            name.line_number,
-            format!("Undefined variable \"{}\".", name.token_type.lexeme()).as_str(),
-        ))
+        Err(Self::undefined(token))
+    }
+
+    fn undefined(token: &Token) -> RuntimeError {
+        RuntimeError::new(
+            token.line_number,
+            format!("Undefined variable \"{}\".", token.token_type.lexeme()).as_str(),
+        )
     }
 }
@@ -1,20 +1,165 @@
-use std::fmt::Display;
+use std::{fmt::Display, sync::Arc};
+
+use crate::interner::Symbol;
+
+use super::{
+    map::{MapKey, OrderedMap},
+    value::Value,
+};
+
+const MAX_DISPLAYED_FRAMES: usize = 20;
+
+// Natives and anonymous functions have fixed names, other functions take theirs from the script
+#[derive(Debug, Clone)]
+pub enum FunctionName {
+    Fixed(&'static str),
+    Bound(Symbol),
+}
+
+impl Display for FunctionName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed(name) => write!(f, "{}", name),
+            Self::Bound(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub function: FunctionName,
+    pub line_number: usize,
+}
+
+impl CallFrame {
+    pub fn new(function: FunctionName, line_number: usize) -> Self {
+        Self {
+            function,
+            line_number,
+        }
+    }
+}
+
+impl Display for CallFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "in {}() called at line {}",
+            self.function, self.line_number
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StackTrace {
+    pub frames: Vec<CallFrame>,
+}
+
+impl StackTrace {
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl Display for StackTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let innermost_first = self.frames.iter().rev();
+
+        if self.frames.len() <= MAX_DISPLAYED_FRAMES {
+            for frame in innermost_first {
+                writeln!(f, "    {}", frame)?;
+            }
+            return Ok(());
+        }
+
+        let shown = MAX_DISPLAYED_FRAMES / 2;
+        for frame in innermost_first.clone().take(shown) {
+            writeln!(f, "    {}", frame)?;
+        }
+        writeln!(
+            f,
+            "    ... {} more frames ...",
+            self.frames.len() - 2 * shown
+        )?;
+        for frame in innermost_first.skip(self.frames.len() - shown) {
+            writeln!(f, "    {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeErrorKind {
+    Error,
+    StepLimitExceeded,
+    Timeout,
+    MemoryLimitExceeded,
+    Cancelled,
+}
+
+impl RuntimeErrorKind {
+    pub fn is_catchable(&self) -> bool {
+        *self == Self::Error
+    }
+}
 
 #[derive(Debug)]
 pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
     pub line_number: usize,
     pub message: String,
+    pub thrown: Option<Value>,
+    pub trace: StackTrace,
+    // The import path of the module the error was raised in, or None for the main script
+    pub module: Option<Arc<str>>,
 }
 
 pub type RuntimeResult<T> = Result<T, RuntimeError>;
 
 impl RuntimeError {
     pub fn new(line_number: usize, message: &str) -> Self {
+        Self::with_kind(RuntimeErrorKind::Error, line_number, message)
+    }
+
+    pub fn with_kind(kind: RuntimeErrorKind, line_number: usize, message: &str) -> Self {
         Self {
+            kind,
             line_number,
             message: message.to_string(),
+            thrown: None,
+            trace: StackTrace::default(),
+            module: None,
         }
     }
+
+    // `description` is the value as print shows it
+    pub fn thrown(line_number: usize, value: Value, description: &str) -> Self {
+        Self {
+            kind: RuntimeErrorKind::Error,
+            line_number,
+            message: format!("Uncaught {}", description),
+            thrown: Some(value),
+            trace: StackTrace::default(),
+            module: None,
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        if let Some(value) = self.thrown {
+            return value;
+        }
+
+        let mut error = OrderedMap::default();
+        error.insert(
+            MapKey::String("message".into()),
+            Value::String(self.message.into()),
+        );
+        error.insert(
+            MapKey::String("line".into()),
+            Value::Integer(self.line_number as i64),
+        );
+        Value::map(error)
+    }
 }
 
 impl Display for RuntimeError {
@@ -22,3 +167,18 @@ impl Display for RuntimeError {
         write!(f, "{}", self.message)
     }
 }
+
+pub enum Interrupt {
+    Break,
+    Continue,
+    Return(Value),
+    Error(RuntimeError),
+}
+
+pub type ExecResult = Result<(), Interrupt>;
+
+impl From<RuntimeError> for Interrupt {
+    fn from(value: RuntimeError) -> Self {
+        Self::Error(value)
+    }
+}
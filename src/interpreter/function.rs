@@ -0,0 +1,23 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::parser::{Ast, FunctionDecl};
+
+use super::{environment::Environment, error::FunctionName};
+
+pub struct Function {
+    // The tree the declaration's parameters and body live in
+    pub ast: Rc<Ast>,
+    pub declaration: Rc<FunctionDecl>,
+    pub closure: Rc<RefCell<Environment>>,
+}
+
+impl Function {
+    pub const NAME: &'static str = "lambda";
+
+    pub fn name(&self) -> FunctionName {
+        match &self.declaration.name {
+            Some(name) => FunctionName::Bound(name.clone()),
+            None => FunctionName::Fixed(Self::NAME),
+        }
+    }
+}
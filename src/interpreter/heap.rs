@@ -0,0 +1,219 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+use super::{environment::Environment, function::Function, map::OrderedMap, value::Value};
+
+// Lists, maps, functions and environments are reference counted, so a cycle among them keeps
+// itself alive: a list pushed into itself, or a closure stored in the scope it captured. Every
+// such object is registered here, and the collector finds dead cycles by trial deletion. It
+// subtracts the references registered objects hold to each other from their counts; whatever
+// remains comes from outside the heap, which makes those objects roots. That covers the globals,
+// the call stack and every temporary the interpreter or a native holds, without listing them.
+// Objects not reachable from a root are garbage and are emptied, which breaks their cycles.
+thread_local! {
+    static HEAP: RefCell<Vec<Object>> = const { RefCell::new(Vec::new()) };
+}
+
+enum Object {
+    List(Weak<RefCell<Vec<Value>>>),
+    Map(Weak<RefCell<OrderedMap>>),
+    Function(Weak<Function>),
+    Environment(Weak<RefCell<Environment>>),
+}
+
+// A registered object that is still alive. Holding it adds one to its strong count.
+enum Live {
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<OrderedMap>>),
+    Function(Rc<Function>),
+    Environment(Rc<RefCell<Environment>>),
+}
+
+pub(super) fn register_list(list: &Rc<RefCell<Vec<Value>>>) {
+    register(Object::List(Rc::downgrade(list)));
+}
+
+pub(super) fn register_map(map: &Rc<RefCell<OrderedMap>>) {
+    register(Object::Map(Rc::downgrade(map)));
+}
+
+pub(super) fn register_function(function: &Rc<Function>) {
+    register(Object::Function(Rc::downgrade(function)));
+}
+
+pub(super) fn register_environment(environment: &Rc<RefCell<Environment>>) {
+    register(Object::Environment(Rc::downgrade(environment)));
+}
+
+fn register(object: Object) {
+    HEAP.with_borrow_mut(|heap| heap.push(object));
+}
+
+// Objects registered since the last collection, including ones that have since been freed
+pub(super) fn registered() -> usize {
+    HEAP.with_borrow(Vec::len)
+}
+
+// Returns how many garbage lists, maps and environments were emptied
+pub(super) fn collect() -> usize {
+    let live = HEAP.with_borrow_mut(|heap| {
+        let live = heap.iter().filter_map(Object::upgrade).collect::<Vec<_>>();
+        heap.clear();
+        heap.extend(live.iter().map(Live::downgrade));
+        live
+    });
+
+    let indices = live
+        .iter()
+        .enumerate()
+        .map(|(index, object)| (object.address(), index))
+        .collect::<HashMap<_, _>>();
+
+    // An object that is borrowed right now can't be scanned, so it is treated as a root. The
+    // objects it refers to then look externally referenced too, which keeps them alive.
+    let mut children = Vec::with_capacity(live.len());
+    let mut internal = vec![0; live.len()];
+    let mut roots = vec![];
+    for (index, object) in live.iter().enumerate() {
+        match object.children() {
+            Some(addresses) => {
+                let addresses = addresses
+                    .into_iter()
+                    .filter_map(|address| indices.get(&address).copied())
+                    .collect::<Vec<_>>();
+                for &child in &addresses {
+                    internal[child] += 1;
+                }
+                children.push(addresses);
+            }
+            None => {
+                roots.push(index);
+                children.push(vec![]);
+            }
+        }
+    }
+
+    // Every count includes the one held by `live`
+    roots.extend((0..live.len()).filter(|&index| live[index].strong_count() - 1 > internal[index]));
+
+    let mut reachable = vec![false; live.len()];
+    while let Some(index) = roots.pop() {
+        if !std::mem::replace(&mut reachable[index], true) {
+            roots.extend(children[index].iter().copied());
+        }
+    }
+
+    // Contents are dropped only after every garbage object is emptied, since dropping them may
+    // free further objects
+    let mut contents = vec![];
+    let mut freed = 0;
+    for (object, reachable) in live.iter().zip(&reachable) {
+        if !reachable && object.clear(&mut contents) {
+            freed += 1;
+        }
+    }
+    drop(contents);
+
+    freed
+}
+
+impl Object {
+    fn upgrade(&self) -> Option<Live> {
+        match self {
+            Self::List(list) => list.upgrade().map(Live::List),
+            Self::Map(map) => map.upgrade().map(Live::Map),
+            Self::Function(function) => function.upgrade().map(Live::Function),
+            Self::Environment(environment) => environment.upgrade().map(Live::Environment),
+        }
+    }
+}
+
+impl Live {
+    fn downgrade(&self) -> Object {
+        match self {
+            Self::List(list) => Object::List(Rc::downgrade(list)),
+            Self::Map(map) => Object::Map(Rc::downgrade(map)),
+            Self::Function(function) => Object::Function(Rc::downgrade(function)),
+            Self::Environment(environment) => Object::Environment(Rc::downgrade(environment)),
+        }
+    }
+
+    fn address(&self) -> *const () {
+        match self {
+            Self::List(list) => Rc::as_ptr(list) as *const (),
+            Self::Map(map) => Rc::as_ptr(map) as *const (),
+            Self::Function(function) => Rc::as_ptr(function) as *const (),
+            Self::Environment(environment) => Rc::as_ptr(environment) as *const (),
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Self::List(list) => Rc::strong_count(list),
+            Self::Map(map) => Rc::strong_count(map),
+            Self::Function(function) => Rc::strong_count(function),
+            Self::Environment(environment) => Rc::strong_count(environment),
+        }
+    }
+
+    // The registered objects this one holds a strong reference to, once per reference
+    fn children(&self) -> Option<Vec<*const ()>> {
+        let mut addresses = vec![];
+        match self {
+            Self::List(list) => {
+                let list = list.try_borrow().ok()?;
+                addresses.extend(list.iter().filter_map(value_address));
+            }
+            Self::Map(map) => {
+                let map = map.try_borrow().ok()?;
+                addresses.extend(map.iter().filter_map(|(_, value)| value_address(value)));
+            }
+            Self::Function(function) => {
+                addresses.push(Rc::as_ptr(&function.closure) as *const ());
+            }
+            Self::Environment(environment) => {
+                let environment = environment.try_borrow().ok()?;
+                addresses.extend(environment.values().filter_map(value_address));
+                addresses.extend(
+                    environment
+                        .enclosing
+                        .as_ref()
+                        .map(|enclosing| Rc::as_ptr(enclosing) as *const ()),
+                );
+            }
+        }
+        Some(addresses)
+    }
+
+    // Functions can't be emptied, but every cycle through one also runs through its closure
+    fn clear(&self, contents: &mut Vec<Box<dyn std::any::Any>>) -> bool {
+        match self {
+            Self::List(list) => match list.try_borrow_mut() {
+                Ok(mut list) => contents.push(Box::new(std::mem::take(&mut *list))),
+                Err(_) => return false,
+            },
+            Self::Map(map) => match map.try_borrow_mut() {
+                Ok(mut map) => contents.push(Box::new(std::mem::take(&mut *map))),
+                Err(_) => return false,
+            },
+            Self::Function(_) => return false,
+            Self::Environment(environment) => match environment.try_borrow_mut() {
+                Ok(mut environment) => contents.push(Box::new(std::mem::take(&mut *environment))),
+                Err(_) => return false,
+            },
+        }
+        true
+    }
+}
+
+fn value_address(value: &Value) -> Option<*const ()> {
+    match value {
+        Value::List(list) => Some(Rc::as_ptr(list) as *const ()),
+        Value::Map(map) => Some(Rc::as_ptr(map) as *const ()),
+        Value::Function(function) => Some(Rc::as_ptr(function) as *const ()),
+        _ => None,
+    }
+}
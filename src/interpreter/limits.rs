@@ -0,0 +1,116 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use super::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+
+// Reading the clock on every node would dominate tight loops.
+const CLOCK_CHECK_INTERVAL: u64 = 256;
+
+#[derive(Debug, Default, Clone)]
+pub struct InterpreterLimits {
+    pub max_steps: Option<u64>,
+    pub timeout: Option<Duration>,
+    // A cumulative allocation budget: the total bytes of strings, collections and printed or
+    // encoded text created during one interpret() call. Values freed along the way are not
+    // credited back, so this is not a cap on live memory, and a long-running script that only
+    // ever holds a little can still exceed it.
+    pub max_allocated_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CancellationHandle(Arc<AtomicBool>);
+
+impl CancellationHandle {
+    // Stops the interpret() call in progress, or the next one if none is running. The request is
+    // cleared when that run ends, so the run after it starts out uncancelled.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Sandbox {
+    pub limits: InterpreterLimits,
+    pub cancellation: CancellationHandle,
+    steps: u64,
+    allocated_bytes: usize,
+    deadline: Option<Instant>,
+}
+
+impl Sandbox {
+    // Cancellation is left alone here, so that a cancel issued just before a run still stops it
+    pub fn start(&mut self) {
+        self.steps = 0;
+        self.allocated_bytes = 0;
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    pub fn finish(&mut self) {
+        self.cancellation.reset();
+    }
+
+    pub fn tick(&mut self, line_number: usize) -> RuntimeResult<()> {
+        self.steps += 1;
+
+        if self.limits.max_steps.is_some_and(|max| self.steps > max) {
+            return Err(RuntimeError::with_kind(
+                RuntimeErrorKind::StepLimitExceeded,
+                line_number,
+                "Instruction budget exceeded.",
+            ));
+        }
+
+        if self.cancellation.is_cancelled() {
+            return Err(RuntimeError::with_kind(
+                RuntimeErrorKind::Cancelled,
+                line_number,
+                "Execution cancelled.",
+            ));
+        }
+
+        if self.steps.is_multiple_of(CLOCK_CHECK_INTERVAL)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(RuntimeError::with_kind(
+                RuntimeErrorKind::Timeout,
+                line_number,
+                "Execution timed out.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn allocate(&mut self, bytes: usize, line_number: usize) -> RuntimeResult<()> {
+        self.allocated_bytes = self.allocated_bytes.saturating_add(bytes);
+
+        if self
+            .limits
+            .max_allocated_bytes
+            .is_some_and(|max| self.allocated_bytes > max)
+        {
+            return Err(RuntimeError::with_kind(
+                RuntimeErrorKind::MemoryLimitExceeded,
+                line_number,
+                "Memory limit exceeded.",
+            ));
+        }
+
+        Ok(())
+    }
+}
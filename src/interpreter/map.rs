@@ -0,0 +1,98 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::value::Value;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Boolean(bool),
+    Integer(i64),
+    Float(u64),
+    String(Arc<str>),
+    Nil,
+}
+
+impl TryFrom<&Value> for MapKey {
+    type Error = ();
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Boolean(value) => Ok(Self::Boolean(*value)),
+            Value::Integer(value) => Ok(Self::Integer(*value)),
+            // Integral floats compare equal to integers, so they have to hash the same
+            Value::Float(value)
+                if value.fract() == 0.0
+                    && *value >= i64::MIN as f64
+                    && *value < i64::MAX as f64 =>
+            {
+                Ok(Self::Integer(*value as i64))
+            }
+            Value::Float(value) => Ok(Self::Float(value.to_bits())),
+            Value::String(value) => Ok(Self::String(value.clone())),
+            Value::Nil => Ok(Self::Nil),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<&MapKey> for Value {
+    fn from(key: &MapKey) -> Self {
+        match key {
+            MapKey::Boolean(value) => Value::Boolean(*value),
+            MapKey::Integer(value) => Value::Integer(*value),
+            MapKey::Float(bits) => Value::Float(f64::from_bits(*bits)),
+            MapKey::String(value) => Value::String(value.clone()),
+            MapKey::Nil => Value::Nil,
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct OrderedMap {
+    entries: Vec<(MapKey, Value)>,
+    indices: HashMap<MapKey, usize>,
+}
+
+impl OrderedMap {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&Value> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: MapKey, value: Value) -> Option<Value> {
+        if let Some(index) = self.indices.get(&key) {
+            return Some(std::mem::replace(&mut self.entries[*index].1, value));
+        }
+
+        self.indices.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<Value> {
+        let index = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+
+        for (key, _) in &self.entries[index..] {
+            if let Some(shifted) = self.indices.get_mut(key) {
+                *shifted -= 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&MapKey, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
@@ -1,95 +1,1058 @@
 pub mod environment;
 pub mod error;
+pub mod function;
+mod heap;
+pub mod limits;
+pub mod map;
+pub mod module;
+pub mod natives;
+pub mod range;
 pub mod value;
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    io,
+    ops::ControlFlow,
+    rc::Rc,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
 
 use environment::Environment;
 pub use error::*;
-use value::{NumberPair, StringPair, Value};
+use function::Function;
+use limits::{CancellationHandle, InterpreterLimits, Sandbox};
+use map::OrderedMap;
+use module::{FileSystemModuleLoader, Module, ModuleLoader};
+use natives::math::Random;
+use value::{DisplayState, NumberPair, StringPair, Value};
 
-use crate::parser::{
-    stmt::{Block, Expression, Print, Stmt, Var, Visitor as StmtVisitor},
-    Assign, Binary, BinaryOp, Expr, Grouping, Literal, LiteralType, Unary, UnaryOp, Variable,
-    Visitor as ExprVisitor,
+use crate::{
+    error::LoxError,
+    interner::{Interner, Symbol},
+    lexer::Lexer,
+    parser::{
+        stmt::{
+            Block, Break, Const, Continue, Export, Expression, ForIn, Import, Print, Return, Stmt,
+            Throw, Try, Var, Visitor as StmtVisitor,
+        },
+        Assign, Ast, Binary, BinaryOp, Call, CompoundAssign, Conditional, Expr, ExprId,
+        FunctionBody, Get, Grouping, Increment, Index, Lambda, List, Literal, LiteralType, Map,
+        NodeId, NodeMap, Parser, Program, SetIndex, StmtId, Unary, UnaryOp, Variable,
+        Visitor as ExprVisitor,
+    },
 };
 
-#[derive(Default)]
+// Every Lox call nests a dozen or so Rust frames, from 2 KiB of native stack in a release build to
+// over 20 KiB in a debug build when the body nests deeply. The stack budget below catches whatever
+// this depth doesn't.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 128;
+
+// How much native stack a run may use, measured from where interpret() was called. A thread from
+// std::thread::spawn gets 2 MiB, and the other half is left to the embedder and to natives.
+pub const DEFAULT_STACK_BUDGET: usize = 1024 * 1024;
+
+// Deeper recursion needs a bigger native stack, which spawn_with_large_stack provides
+pub const LARGE_STACK_MAX_CALL_DEPTH: usize = 4096;
+const LARGE_STACK_SIZE: usize = 256 * 1024 * 1024;
+const LARGE_STACK_BUDGET: usize = LARGE_STACK_SIZE - DEFAULT_STACK_BUDGET;
+
+// Objects registered with the heap before the first collection. Later collections wait until the
+// heap has doubled since the last one, so the cost of collecting stays proportional to allocation.
+pub const DEFAULT_GC_THRESHOLD: usize = 10_000;
+
 pub struct Interpreter {
     environment: Rc<RefCell<Environment>>,
+    // The tree of the code running now: the script, a module, or the body of a function
+    ast: Rc<Ast>,
+    // Identifiers and string literals of every script parsed so far
+    interner: Interner,
+    call_stack: Vec<CallFrame>,
+    max_call_depth: usize,
+    stack_budget: usize,
+    // Address of the native stack when the current run started
+    stack_base: usize,
+    sandbox: Sandbox,
+    current_line: usize,
+    module_loader: Box<dyn ModuleLoader>,
+    // A module maps to None while it is still executing, which is how cyclic imports are caught
+    modules: HashMap<Arc<str>, Option<Rc<Module>>>,
+    current_module: Option<Arc<str>>,
+    // Ids handed out by every parse so far, which keeps them unique across modules
+    node_count: usize,
+    // The module each import statement resolved to, so re-running parsed code skips resolution
+    imports: NodeMap<Rc<Module>>,
+    random: Random,
+    filesystem_access: bool,
+    script_args: Vec<String>,
+    gc_threshold: usize,
+    next_collection: usize,
+    // Collects before every statement and allocation, which shakes out collector bugs in tests
+    gc_stress: bool,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
-    pub fn interpret(&mut self, stmts: &[Stmt]) -> RuntimeResult<()> {
-        for stmt in stmts {
-            self.execute(stmt)?;
+    pub fn new() -> Self {
+        let mut interner = Interner::default();
+        Self {
+            environment: Self::global_environment(&mut interner),
+            ast: Rc::new(Ast::new(NodeId::new(0))),
+            interner,
+            call_stack: vec![],
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            stack_budget: DEFAULT_STACK_BUDGET,
+            stack_base: 0,
+            sandbox: Sandbox::default(),
+            current_line: 1,
+            module_loader: Box::new(FileSystemModuleLoader),
+            modules: HashMap::new(),
+            current_module: None,
+            node_count: 0,
+            imports: NodeMap::default(),
+            random: Random::from_time(),
+            filesystem_access: false,
+            script_args: vec![],
+            gc_threshold: DEFAULT_GC_THRESHOLD,
+            next_collection: DEFAULT_GC_THRESHOLD,
+            gc_stress: false,
         }
-        Ok(())
     }
 
-    pub fn evaluate(&mut self, expr: &Expr) -> RuntimeResult<Value> {
+    // Runs `run` on a new thread with enough native stack for LARGE_STACK_MAX_CALL_DEPTH, handing
+    // it an interpreter configured for that depth. Interpreters aren't Send, so it is made there.
+    pub fn spawn_with_large_stack<T, F>(run: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce(&mut Interpreter) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        thread::Builder::new()
+            .name("interpreter".to_string())
+            .stack_size(LARGE_STACK_SIZE)
+            .spawn(move || {
+                let mut interpreter = Interpreter::new();
+                interpreter.set_max_call_depth(LARGE_STACK_MAX_CALL_DEPTH);
+                interpreter.set_stack_budget(LARGE_STACK_BUDGET);
+                run(&mut interpreter)
+            })
+    }
+
+    pub fn set_module_loader(&mut self, module_loader: impl ModuleLoader + 'static) {
+        self.module_loader = Box::new(module_loader);
+        self.imports = NodeMap::default();
+    }
+
+    pub fn set_filesystem_access(&mut self, enabled: bool) {
+        self.filesystem_access = enabled;
+        self.imports = NodeMap::default();
+    }
+
+    pub fn set_script_args(&mut self, args: Vec<String>) {
+        self.script_args = args;
+    }
+
+    pub fn set_script_path(&mut self, path: &str) {
+        let id = self
+            .module_loader
+            .resolve(None, path)
+            .unwrap_or_else(|_| path.into());
+        self.modules.insert(id.clone(), None);
+        self.current_module = Some(id);
+    }
+
+    pub fn set_limits(&mut self, limits: InterpreterLimits) {
+        self.sandbox.limits = limits;
+    }
+
+    pub fn cancellation_handle(&self) -> CancellationHandle {
+        self.sandbox.cancellation.clone()
+    }
+
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    // Embedders running on a thread with a known stack size can raise this along with the depth
+    pub fn set_stack_budget(&mut self, bytes: usize) {
+        self.stack_budget = bytes;
+    }
+
+    // The size of the interner, which only holds names and literals that parsed code still uses
+    pub fn interned_strings(&self) -> usize {
+        self.interner.len()
+    }
+
+    pub fn set_gc_threshold(&mut self, threshold: usize) {
+        self.gc_threshold = threshold;
+        self.next_collection = threshold;
+    }
+
+    pub fn set_gc_stress(&mut self, enabled: bool) {
+        self.gc_stress = enabled;
+    }
+
+    // Frees lists, maps and closures that are only kept alive by cycles among themselves, and
+    // returns how many objects were emptied to do so. Runs on its own as the heap grows.
+    pub fn collect_garbage(&mut self) -> usize {
+        let freed = heap::collect();
+        self.next_collection = self.gc_threshold.max(2 * heap::registered());
+        freed
+    }
+
+    fn maybe_collect_garbage(&mut self) {
+        if self.gc_stress || heap::registered() >= self.next_collection {
+            self.collect_garbage();
+        }
+    }
+
+    // Every parse continues the numbering of the ones before, so side tables keyed by NodeId
+    // can't confuse nodes of the main script with those of the modules it imports
+    pub fn parse(&mut self, source: &str) -> Result<Program, LoxError> {
+        self.interner.reclaim();
+        let tokens = Lexer::new(source, &mut self.interner).lex()?;
+        let mut ast = Ast::new(NodeId::new(self.node_count));
+        let statements = Parser::new(&tokens, &mut ast).parse()?;
+        self.node_count = ast.end().index();
+        Ok(Program {
+            ast: Rc::new(ast),
+            statements,
+        })
+    }
+
+    pub fn interpret(&mut self, program: &Program) -> RuntimeResult<()> {
+        self.sandbox.start();
+        self.stack_base = stack_address();
+        let result = self.execute_top_level(program);
+        self.sandbox.finish();
+        result
+    }
+
+    fn execute_top_level(&mut self, program: &Program) -> RuntimeResult<()> {
+        let previous_ast = std::mem::replace(&mut self.ast, program.ast.clone());
+        let result = program
+            .statements
+            .iter()
+            .try_for_each(|stmt| match self.execute(*stmt) {
+                Err(Interrupt::Error(err)) => Err(err),
+                Ok(()) | Err(Interrupt::Break | Interrupt::Continue | Interrupt::Return(_)) => {
+                    Ok(())
+                }
+            });
+        self.ast = previous_ast;
+        result
+    }
+
+    // The tree is held for the duration of the visit, since a call may replace self.ast
+    fn evaluate(&mut self, expr: ExprId) -> RuntimeResult<Value> {
+        let ast = self.ast.clone();
+        let expr = &ast[expr];
+        self.current_line = expr.line_number();
+        self.sandbox.tick(self.current_line)?;
+        self.check_stack(self.current_line)?;
         expr.accept(self)
     }
 
-    fn execute(&mut self, stmt: &Stmt) -> RuntimeResult<()> {
-        stmt.accept(self)
+    // The call depth alone can't bound native stack use, since how much a call takes depends on
+    // how deeply its expressions and statements nest
+    fn check_stack(&self, line_number: usize) -> RuntimeResult<()> {
+        if stack_address().abs_diff(self.stack_base) > self.stack_budget {
+            return Err(RuntimeError::new(line_number, "Stack overflow."));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn allocate(&mut self, bytes: usize, line_number: usize) -> RuntimeResult<()> {
+        if self.gc_stress {
+            self.collect_garbage();
+        }
+        self.sandbox.allocate(bytes, line_number)
+    }
+
+    pub(crate) fn allocate_values(
+        &mut self,
+        count: usize,
+        line_number: usize,
+    ) -> RuntimeResult<()> {
+        self.allocate(count.saturating_mul(size_of::<Value>()), line_number)
+    }
+
+    // Renders a value the way print shows it. A list stored in many places can print far larger
+    // than it is, so the text is charged to the sandbox as it grows.
+    pub(crate) fn render(&mut self, value: &Value, line_number: usize) -> RuntimeResult<String> {
+        let mut output = RenderedText {
+            interpreter: self,
+            text: String::new(),
+            line_number,
+            error: None,
+        };
+        match value.write(&mut output, &mut DisplayState::default()) {
+            Ok(()) => Ok(output.text),
+            Err(_) => Err(output
+                .error
+                .unwrap_or_else(|| RuntimeError::new(line_number, "Value could not be printed."))),
+        }
+    }
+
+    // Builtins get a scope of their own around the globals, so a script can declare its own pi
+    fn global_environment(interner: &mut Interner) -> Rc<RefCell<Environment>> {
+        let builtins = Environment::new(None);
+        natives::define_globals(&mut builtins.borrow_mut(), interner);
+        Environment::new(Some(builtins))
+    }
+
+    fn execute(&mut self, stmt: StmtId) -> ExecResult {
+        self.sandbox.tick(self.current_line)?;
+        self.check_stack(self.current_line)?;
+        self.maybe_collect_garbage();
+        let ast = self.ast.clone();
+        ast[stmt].accept(self)
     }
 
     fn execute_block(
         &mut self,
-        statements: &[Stmt],
+        statements: &[StmtId],
         new_environment: Rc<RefCell<Environment>>,
-    ) -> RuntimeResult<()> {
-        let previous_environment = self.environment.clone();
-        self.environment = new_environment;
+    ) -> ExecResult {
+        let previous_environment = std::mem::replace(&mut self.environment, new_environment);
+
+        let result = statements
+            .iter()
+            .try_for_each(|statement| self.execute(*statement));
+
+        self.environment = previous_environment;
+
+        result
+    }
+
+    fn execute_iteration(
+        &mut self,
+        stmt: &ForIn,
+        value: Value,
+    ) -> Result<ControlFlow<()>, Interrupt> {
+        let new_environment = Environment::new(Some(self.environment.clone()));
+        new_environment
+            .borrow_mut()
+            .define(stmt.name.clone(), value);
+
+        match self.execute_block(std::slice::from_ref(&stmt.body), new_environment) {
+            Ok(()) | Err(Interrupt::Continue) => Ok(ControlFlow::Continue(())),
+            Err(Interrupt::Break) => Ok(ControlFlow::Break(())),
+            Err(interrupt) => Err(interrupt),
+        }
+    }
+
+    fn load_module(&mut self, path: &str, line_number: usize) -> RuntimeResult<Rc<Module>> {
+        if !self.filesystem_access && self.module_loader.uses_filesystem() {
+            return Err(Self::module_error(
+                path,
+                "filesystem access is disabled.",
+                line_number,
+            ));
+        }
+
+        let id = self
+            .module_loader
+            .resolve(self.current_module.as_deref(), path)
+            .map_err(|err| Self::module_error(path, &err, line_number))?;
+
+        match self.modules.get(&id) {
+            Some(Some(module)) => return Ok(module.clone()),
+            Some(None) => {
+                return Err(RuntimeError::new(
+                    line_number,
+                    format!("Cyclic import of module \"{}\".", path).as_str(),
+                ))
+            }
+            None => {}
+        }
+
+        self.modules.insert(id.clone(), None);
+        let result = self.execute_module(&id, path, line_number);
+        match &result {
+            Ok(module) => self.modules.insert(id, Some(module.clone())),
+            Err(_) => self.modules.remove(&id),
+        };
+        result
+    }
+
+    fn execute_module(
+        &mut self,
+        id: &Arc<str>,
+        path: &str,
+        line_number: usize,
+    ) -> RuntimeResult<Rc<Module>> {
+        let source = self
+            .module_loader
+            .load(id)
+            .map_err(|err| Self::module_error(path, &err, line_number))?;
+        let program = self
+            .parse(&source)
+            .map_err(|err| Self::module_error(path, err.to_string().trim_end(), line_number))?;
+
+        let exports = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match &program.ast[*stmt] {
+                Stmt::Export(export) => match &program.ast[export.declaration] {
+                    Stmt::Var(var) => Some(var.name.clone()),
+                    Stmt::Const(constant) => Some(constant.name.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        let environment = Self::global_environment(&mut self.interner);
+        let previous_environment = std::mem::replace(&mut self.environment, environment.clone());
+        let previous_module = self.current_module.replace(id.clone());
+
+        let result = self.execute_top_level(&program);
 
-        for statement in statements {
-            self.execute(statement)?;
+        self.environment = previous_environment;
+        self.current_module = previous_module;
+        // Line numbers inside the module mean nothing without its path. A nested import has
+        // already claimed the error, so only the innermost module is recorded.
+        result.map_err(|mut err| {
+            err.module.get_or_insert_with(|| path.into());
+            err
+        })?;
+
+        Ok(Rc::new(Module::new(path.into(), environment, exports)))
+    }
+
+    fn module_error(path: &str, message: &str, line_number: usize) -> RuntimeError {
+        RuntimeError::new(
+            line_number,
+            format!("Could not load module \"{}\": {}", path, message).as_str(),
+        )
+    }
+
+    fn with_frame<T>(
+        &mut self,
+        frame: CallFrame,
+        call: impl FnOnce(&mut Self) -> RuntimeResult<T>,
+    ) -> RuntimeResult<T> {
+        if self.call_stack.len() >= self.max_call_depth
+            || self.check_stack(frame.line_number).is_err()
+        {
+            let mut err = RuntimeError::new(frame.line_number, "Stack overflow.");
+            err.trace.frames = self.call_stack.clone();
+            return Err(err);
         }
 
+        self.call_stack.push(frame);
+        let result = call(self).map_err(|mut err| {
+            if err.trace.is_empty() {
+                err.trace.frames = self.call_stack.clone();
+            }
+            err
+        });
+        self.call_stack.pop();
+        result
+    }
+
+    pub(crate) fn call_value(
+        &mut self,
+        callee: &Value,
+        arguments: Vec<Value>,
+        named_arguments: Vec<(Symbol, Value)>,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        match callee {
+            Value::NativeFunction(native) => {
+                let frame = CallFrame::new(FunctionName::Fixed(native.name), line_number);
+                self.with_frame(frame, |interpreter| {
+                    if let Some((name, _)) = named_arguments.first() {
+                        return Err(RuntimeError::new(
+                            line_number,
+                            format!("{}() got an unexpected argument \"{}\".", native.name, name)
+                                .as_str(),
+                        ));
+                    }
+                    native.check_arity(arguments.len(), line_number)?;
+                    native.call(interpreter, &arguments, line_number)
+                })
+            }
+            Value::Function(function) => {
+                let frame = CallFrame::new(function.name(), line_number);
+                self.with_frame(frame, |interpreter| {
+                    interpreter.call_function(function, arguments, named_arguments, line_number)
+                })
+            }
+            _ => Err(RuntimeError::new(line_number, "Can only call functions.")),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        function: &Function,
+        arguments: Vec<Value>,
+        named_arguments: Vec<(Symbol, Value)>,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        let environment = Environment::new(Some(function.closure.clone()));
+        let previous_environment = std::mem::replace(&mut self.environment, environment);
+        let previous_ast = std::mem::replace(&mut self.ast, function.ast.clone());
+
+        let declaration = &function.declaration;
+        let result = self
+            .bind_arguments(function, arguments, named_arguments, line_number)
+            .and_then(|()| match &declaration.body {
+                FunctionBody::Expression(body) => self.evaluate(*body),
+                FunctionBody::Block(body) => {
+                    match body.iter().try_for_each(|stmt| self.execute(*stmt)) {
+                        Ok(()) | Err(Interrupt::Break | Interrupt::Continue) => Ok(Value::Nil),
+                        Err(Interrupt::Return(value)) => Ok(value),
+                        Err(Interrupt::Error(err)) => Err(err),
+                    }
+                }
+            });
+
         self.environment = previous_environment;
+        self.ast = previous_ast;
+        result
+    }
+
+    // Defaults are evaluated in the new function scope, so they can refer to earlier parameters
+    fn bind_arguments(
+        &mut self,
+        function: &Function,
+        arguments: Vec<Value>,
+        named_arguments: Vec<(Symbol, Value)>,
+        line_number: usize,
+    ) -> RuntimeResult<()> {
+        let declaration = &function.declaration;
+        let params = &declaration.params;
+        let mut bound = vec![None; params.len()];
+        let mut arguments = arguments.into_iter();
+        for (slot, argument) in bound.iter_mut().zip(arguments.by_ref()) {
+            *slot = Some(argument);
+        }
+
+        let extra = arguments.collect::<Vec<_>>();
+        if !extra.is_empty() && declaration.rest.is_none() {
+            return Err(RuntimeError::new(
+                line_number,
+                format!(
+                    "{}() expected at most {} arguments but got {}.",
+                    function.name(),
+                    params.len(),
+                    params.len() + extra.len()
+                )
+                .as_str(),
+            ));
+        }
+
+        for (name, value) in named_arguments {
+            let Some(index) = params.iter().position(|param| param.name == name) else {
+                return Err(RuntimeError::new(
+                    line_number,
+                    format!(
+                        "{}() got an unexpected argument \"{}\".",
+                        function.name(),
+                        name
+                    )
+                    .as_str(),
+                ));
+            };
+            if bound[index].is_some() {
+                return Err(RuntimeError::new(
+                    line_number,
+                    format!(
+                        "{}() got multiple values for parameter \"{}\".",
+                        function.name(),
+                        name
+                    )
+                    .as_str(),
+                ));
+            }
+            bound[index] = Some(value);
+        }
+
+        for (param, value) in params.iter().zip(bound) {
+            let value = match (value, param.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => self.evaluate(default)?,
+                (None, None) => {
+                    return Err(RuntimeError::new(
+                        line_number,
+                        format!(
+                            "{}() missing argument for parameter \"{}\".",
+                            function.name(),
+                            param.name
+                        )
+                        .as_str(),
+                    ))
+                }
+            };
+            self.environment
+                .borrow_mut()
+                .define(param.name.clone(), value);
+        }
+
+        if let Some(rest) = &declaration.rest {
+            self.allocate_values(extra.len(), line_number)?;
+            self.environment
+                .borrow_mut()
+                .define(rest.clone(), Value::list(extra));
+        }
 
         Ok(())
     }
 
+    fn apply_binary(
+        &mut self,
+        operator: &BinaryOp,
+        left: Value,
+        right: Value,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        match operator {
+            BinaryOp::Mul => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Self::arithmetic(pair, i64::checked_mul, |l, r| l * r, line_number),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Div => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => {
+                    let (left, right) = pair.as_floats();
+                    Ok(Value::Float(left / right))
+                }
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::IntDiv => match NumberPair::try_from((&left, &right)) {
+                Ok(NumberPair::Integers(_, 0)) => {
+                    Err(RuntimeError::new(line_number, "Division by zero."))
+                }
+                Ok(pair) => {
+                    Self::arithmetic(pair, Self::floor_div, |l, r| (l / r).floor(), line_number)
+                }
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Mod => match NumberPair::try_from((&left, &right)) {
+                Ok(NumberPair::Integers(_, 0)) => {
+                    Err(RuntimeError::new(line_number, "Division by zero."))
+                }
+                Ok(pair) => Self::arithmetic(
+                    pair,
+                    Self::floor_mod,
+                    |l, r| l - r * (l / r).floor(),
+                    line_number,
+                ),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Pow => match NumberPair::try_from((&left, &right)) {
+                Ok(NumberPair::Integers(base, exponent)) if exponent >= 0 => {
+                    u32::try_from(exponent)
+                        .ok()
+                        .and_then(|exponent| base.checked_pow(exponent))
+                        .map(Value::Integer)
+                        .ok_or_else(|| RuntimeError::new(line_number, "Integer overflow."))
+                }
+                Ok(pair) => {
+                    let (base, exponent) = pair.as_floats();
+                    Ok(Value::Float(base.powf(exponent)))
+                }
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::BitAnd => Self::bitwise(&left, &right, |l, r| Some(l & r), line_number),
+            BinaryOp::BitOr => Self::bitwise(&left, &right, |l, r| Some(l | r), line_number),
+            BinaryOp::BitXor => Self::bitwise(&left, &right, |l, r| Some(l ^ r), line_number),
+            BinaryOp::ShiftLeft => Self::bitwise(
+                &left,
+                &right,
+                |l, r| u32::try_from(r).ok().and_then(|r| l.checked_shl(r)),
+                line_number,
+            ),
+            BinaryOp::ShiftRight => Self::bitwise(
+                &left,
+                &right,
+                |l, r| u32::try_from(r).ok().and_then(|r| l.checked_shr(r)),
+                line_number,
+            ),
+            BinaryOp::Minus => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Self::arithmetic(pair, i64::checked_sub, |l, r| l - r, line_number),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Plus => {
+                if let Ok(pair) = NumberPair::try_from((&left, &right)) {
+                    return Self::arithmetic(pair, i64::checked_add, |l, r| l + r, line_number);
+                }
+                if let Ok(pair) = StringPair::try_from((&left, &right)) {
+                    self.allocate(pair.0.len() + pair.1.len(), line_number)?;
+                    let concatenated = [&*pair.0, &*pair.1].concat();
+                    return Ok(Value::String(concatenated.into()));
+                }
+                Err(RuntimeError::new(
+                    line_number,
+                    "Operands must be two numbers or two strings.",
+                ))
+            }
+            BinaryOp::Greater => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Ok(Value::Boolean(pair.compare() == Some(Ordering::Greater))),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Less => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Ok(Value::Boolean(pair.compare() == Some(Ordering::Less))),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::GreaterEqual => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Ok(Value::Boolean(matches!(
+                    pair.compare(),
+                    Some(Ordering::Greater | Ordering::Equal)
+                ))),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::LessEqual => match NumberPair::try_from((&left, &right)) {
+                Ok(pair) => Ok(Value::Boolean(matches!(
+                    pair.compare(),
+                    Some(Ordering::Less | Ordering::Equal)
+                ))),
+                Err(_) => Err(RuntimeError::new(line_number, "Operands must be numbers.")),
+            },
+            BinaryOp::Equal => Ok(Value::Boolean(left == right)),
+            BinaryOp::NotEqual => Ok(Value::Boolean(left != right)),
+        }
+    }
+
+    fn get_index(object: &Value, index: &Value, line_number: usize) -> RuntimeResult<Value> {
+        match object {
+            Value::List(list) => {
+                let list = list.borrow();
+                let index = natives::list::element_index(index, list.len(), line_number)?;
+                Ok(list[index].clone())
+            }
+            Value::Map(map) => {
+                let key = natives::map::map_key(index, line_number)?;
+                Ok(map.borrow().get(&key).cloned().unwrap_or(Value::Nil))
+            }
+            Value::Range(range) => {
+                let len = usize::try_from(range.len()).unwrap_or(usize::MAX);
+                let index = natives::list::element_index(index, len, line_number)?;
+                Ok(range.get(index as u64).unwrap_or(Value::Nil))
+            }
+            _ => Err(RuntimeError::new(
+                line_number,
+                "Only lists, maps and ranges can be indexed.",
+            )),
+        }
+    }
+
+    fn set_index_value(
+        &mut self,
+        object: &Value,
+        index: &Value,
+        value: Value,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        match object {
+            Value::List(list) => {
+                let mut list = list.borrow_mut();
+                let index = natives::list::element_index(index, list.len(), line_number)?;
+                list[index] = value.clone();
+                Ok(value)
+            }
+            Value::Map(map) => {
+                let key = natives::map::map_key(index, line_number)?;
+                if !map.borrow().contains_key(&key) {
+                    self.allocate_values(2, line_number)?;
+                }
+                map.borrow_mut().insert(key, value.clone());
+                Ok(value)
+            }
+            _ => Err(RuntimeError::new(
+                line_number,
+                "Only lists and maps can be indexed.",
+            )),
+        }
+    }
+
+    fn update(
+        &mut self,
+        target: ExprId,
+        line_number: usize,
+        compute: impl FnOnce(&mut Self, Value) -> RuntimeResult<Value>,
+    ) -> RuntimeResult<(Value, Value)> {
+        let ast = self.ast.clone();
+        match &ast[target] {
+            Expr::Variable(variable) => {
+                let old = self.environment.borrow().get(&variable.name)?;
+                let new = compute(self, old.clone())?;
+                self.environment
+                    .borrow_mut()
+                    .assign(&variable.name, new.clone())?;
+                Ok((old, new))
+            }
+            Expr::Index(target) => {
+                let object = self.evaluate(target.object)?;
+                let index = self.evaluate(target.index)?;
+                let old = Self::get_index(&object, &index, line_number)?;
+                let new = compute(self, old.clone())?;
+                self.set_index_value(&object, &index, new.clone(), line_number)?;
+                Ok((old, new))
+            }
+            _ => Err(RuntimeError::new(line_number, "Invalid assignment target.")),
+        }
+    }
+
+    fn arithmetic(
+        pair: NumberPair,
+        integer_op: fn(i64, i64) -> Option<i64>,
+        float_op: fn(f64, f64) -> f64,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        match pair {
+            NumberPair::Integers(left, right) => integer_op(left, right)
+                .map(Value::Integer)
+                .ok_or_else(|| RuntimeError::new(line_number, "Integer overflow.")),
+            NumberPair::Floats(left, right) => Ok(Value::Float(float_op(left, right))),
+        }
+    }
+
+    fn bitwise(
+        left: &Value,
+        right: &Value,
+        op: fn(i64, i64) -> Option<i64>,
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => op(*left, *right)
+                .map(Value::Integer)
+                .ok_or_else(|| RuntimeError::new(line_number, "Shift amount out of range.")),
+            _ => Err(RuntimeError::new(line_number, "Operands must be integers.")),
+        }
+    }
+
+    fn floor_div(left: i64, right: i64) -> Option<i64> {
+        let quotient = left.checked_div(right)?;
+        if left % right != 0 && (left < 0) != (right < 0) {
+            Some(quotient - 1)
+        } else {
+            Some(quotient)
+        }
+    }
+
+    fn floor_mod(left: i64, right: i64) -> Option<i64> {
+        let remainder = left.checked_rem(right)?;
+        if remainder != 0 && (remainder < 0) != (right < 0) {
+            Some(remainder + right)
+        } else {
+            Some(remainder)
+        }
+    }
+
+    fn is_abort(result: &ExecResult) -> bool {
+        matches!(result, Err(Interrupt::Error(err)) if !err.kind.is_catchable())
+    }
+
     fn is_truthy(value: &Value) -> bool {
         match value {
             Value::Boolean(value) => *value,
-            Value::Number(_) => true,
+            Value::Integer(_) => true,
+            Value::Float(_) => true,
             Value::String(_) => true,
+            Value::List(_) => true,
+            Value::Map(_) => true,
+            Value::Range(_) => true,
+            Value::NativeFunction(_) => true,
+            Value::Function(_) => true,
+            Value::Module(_) => true,
             Value::Nil => false,
         }
     }
 }
 
-impl StmtVisitor<RuntimeResult<()>> for Interpreter {
-    fn expression(&mut self, stmt: &Expression) -> RuntimeResult<()> {
-        self.evaluate(&stmt.expression)?;
+impl StmtVisitor<ExecResult> for Interpreter {
+    fn expression(&mut self, stmt: &Expression) -> ExecResult {
+        self.evaluate(stmt.expression)?;
         Ok(())
     }
 
-    fn print(&mut self, stmt: &Print) -> RuntimeResult<()> {
-        let value = self.evaluate(&stmt.expression)?;
-        println!("{}", value);
+    fn print(&mut self, stmt: &Print) -> ExecResult {
+        let value = self.evaluate(stmt.expression)?;
+        println!("{}", self.render(&value, self.current_line)?);
         Ok(())
     }
 
-    fn var(&mut self, stmt: &Var) -> RuntimeResult<()> {
-        let value = if let Some(init) = &stmt.initializer {
+    fn var(&mut self, stmt: &Var) -> ExecResult {
+        let value = if let Some(init) = stmt.initializer {
             self.evaluate(init)?
         } else {
             Value::Nil
         };
 
         let mut env = self.environment.borrow_mut();
-        env.define(&stmt.name, value);
+        env.declare(stmt.name.clone(), value, stmt.line_number)?;
+
+        Ok(())
+    }
 
+    fn const_stmt(&mut self, stmt: &Const) -> ExecResult {
+        let value = self.evaluate(stmt.initializer)?;
+        self.environment.borrow_mut().declare_constant(
+            stmt.name.clone(),
+            value,
+            stmt.line_number,
+        )?;
         Ok(())
     }
 
-    fn block(&mut self, stmt: &Block) -> RuntimeResult<()> {
+    fn block(&mut self, stmt: &Block) -> ExecResult {
         let new_environment = Environment::new(Some(self.environment.clone())); // New block environment
-        self.execute_block(&stmt.statements, new_environment)?;
+        self.execute_block(&stmt.statements, new_environment)
+    }
+
+    fn break_stmt(&mut self, _stmt: &Break) -> ExecResult {
+        Err(Interrupt::Break)
+    }
+
+    fn continue_stmt(&mut self, _stmt: &Continue) -> ExecResult {
+        Err(Interrupt::Continue)
+    }
+
+    fn return_stmt(&mut self, stmt: &Return) -> ExecResult {
+        let value = match stmt.value {
+            Some(value) => self.evaluate(value)?,
+            None => Value::Nil,
+        };
+        Err(Interrupt::Return(value))
+    }
+
+    fn throw(&mut self, stmt: &Throw) -> ExecResult {
+        let value = self.evaluate(stmt.value)?;
+        let description = self.render(&value, stmt.line_number)?;
+        Err(RuntimeError::thrown(stmt.line_number, value, &description).into())
+    }
+
+    fn try_stmt(&mut self, stmt: &Try) -> ExecResult {
+        let mut result =
+            self.execute_block(&stmt.body, Environment::new(Some(self.environment.clone())));
+
+        // Resource limit aborts unwind straight through catch and finally
+        if Self::is_abort(&result) {
+            return result;
+        }
+
+        if let (Some(name), Some(catch_body)) = (&stmt.catch_name, &stmt.catch_body) {
+            if let Err(Interrupt::Error(err)) = result {
+                let new_environment = Environment::new(Some(self.environment.clone()));
+                new_environment
+                    .borrow_mut()
+                    .define(name.clone(), err.into_value());
+                result = self.execute_block(catch_body, new_environment);
+                if Self::is_abort(&result) {
+                    return result;
+                }
+            }
+        }
+
+        if let Some(finally_body) = &stmt.finally_body {
+            self.execute_block(
+                finally_body,
+                Environment::new(Some(self.environment.clone())),
+            )?;
+        }
+
+        result
+    }
+
+    fn import(&mut self, stmt: &Import) -> ExecResult {
+        let module = match self.imports.get(stmt.id) {
+            Some(module) => module.clone(),
+            None => {
+                let module = self.load_module(&stmt.path, stmt.line_number)?;
+                self.imports.insert(stmt.id, module.clone());
+                module
+            }
+        };
+
+        let mut env = self.environment.borrow_mut();
+        if let Some(alias) = &stmt.alias {
+            env.declare(
+                alias.clone(),
+                Value::Module(module.clone()),
+                stmt.line_number,
+            )?;
+        }
+        for name in &stmt.names {
+            let value = module.get(name).ok_or_else(|| {
+                RuntimeError::new(
+                    stmt.line_number,
+                    format!("Module \"{}\" has no export \"{}\".", stmt.path, name).as_str(),
+                )
+            })?;
+            // An exported constant stays one in the importing script
+            if module.is_constant(name) {
+                env.declare_constant(name.clone(), value, stmt.line_number)?;
+            } else {
+                env.declare(name.clone(), value, stmt.line_number)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn export(&mut self, stmt: &Export) -> ExecResult {
+        self.execute(stmt.declaration)
+    }
+
+    fn for_in(&mut self, stmt: &ForIn) -> ExecResult {
+        match self.evaluate(stmt.iterable)? {
+            Value::List(list) => {
+                let mut index = 0;
+                loop {
+                    let element = match list.borrow().get(index) {
+                        Some(element) => element.clone(),
+                        None => break,
+                    };
+                    if self.execute_iteration(stmt, element)?.is_break() {
+                        break;
+                    }
+                    index += 1;
+                }
+            }
+            Value::Map(map) => {
+                let keys = map
+                    .borrow()
+                    .iter()
+                    .map(|(key, _)| key.into())
+                    .collect::<Vec<Value>>();
+                for key in keys {
+                    if self.execute_iteration(stmt, key)?.is_break() {
+                        break;
+                    }
+                }
+            }
+            Value::String(string) => {
+                for char in string.chars() {
+                    let value = Value::String(char.to_string().into());
+                    if self.execute_iteration(stmt, value)?.is_break() {
+                        break;
+                    }
+                }
+            }
+            Value::Range(range) => {
+                for value in range.iter() {
+                    if self.execute_iteration(stmt, value)?.is_break() {
+                        break;
+                    }
+                }
+            }
+            _ => {
+                return Err(RuntimeError::new(
+                    stmt.line_number,
+                    "Can only iterate over lists, maps, strings and ranges.",
+                )
+                .into())
+            }
+        }
+
         Ok(())
     }
 }
@@ -100,98 +1063,47 @@ impl ExprVisitor<RuntimeResult<Value>> for Interpreter {
             LiteralType::Nil => Ok(Value::Nil),
             LiteralType::Bool { value } => Ok(Value::Boolean(*value)),
             LiteralType::String { value } => Ok(Value::String(value.clone())),
-            LiteralType::Number { value } => Ok(Value::Number(*value)),
+            LiteralType::Integer { value } => Ok(Value::Integer(*value)),
+            LiteralType::Float { value } => Ok(Value::Float(*value)),
         }
     }
 
     fn grouping(&mut self, expr: &Grouping) -> RuntimeResult<Value> {
-        self.evaluate(&expr.expression)
+        self.evaluate(expr.expression)
     }
 
     fn unary(&mut self, expr: &Unary) -> RuntimeResult<Value> {
-        let right = self.evaluate(&expr.right)?;
+        let right = self.evaluate(expr.right)?;
 
         match expr.operator {
             UnaryOp::Minus => match right {
-                Value::Number(value) => Ok(Value::Number(value * -1.0)),
+                Value::Integer(value) => value
+                    .checked_neg()
+                    .map(Value::Integer)
+                    .ok_or_else(|| RuntimeError::new(expr.line_number, "Integer overflow.")),
+                Value::Float(value) => Ok(Value::Float(-value)),
                 _ => Err(RuntimeError::new(
                     expr.line_number,
                     "Operand must be a number",
                 )),
             },
             UnaryOp::Bang => Ok(Value::Boolean(!Self::is_truthy(&right))),
-        }
-    }
-
-    fn binary(&mut self, expr: &Binary) -> RuntimeResult<Value> {
-        let left = self.evaluate(&expr.left)?;
-        let right = self.evaluate(&expr.right)?;
-
-        match expr.operator {
-            BinaryOp::Mul => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Number(pair.0 * pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::Div => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Number(pair.0 / pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::Minus => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Number(pair.0 - pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::Plus => {
-                if let Ok(pair) = NumberPair::try_from((&left, &right)) {
-                    return Ok(Value::Number(pair.0 + pair.1));
-                }
-                if let Ok(pair) = StringPair::try_from((&left, &right)) {
-                    let concatenated = String::from_iter([pair.0, pair.1]);
-                    return Ok(Value::String(concatenated));
-                }
-                Err(RuntimeError::new(expr.line_number, "blabla"))
-            }
-            BinaryOp::Greater => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Boolean(pair.0 > pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::Less => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Boolean(pair.0 < pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::GreaterEqual => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Boolean(pair.0 >= pair.1)),
-                Err(_) => Err(RuntimeError::new(
-                    expr.line_number,
-                    "Operands must be numbers.",
-                )),
-            },
-            BinaryOp::LessEqual => match NumberPair::try_from((&left, &right)) {
-                Ok(pair) => Ok(Value::Boolean(pair.0 <= pair.1)),
-                Err(_) => Err(RuntimeError::new(
+            UnaryOp::BitNot => match right {
+                Value::Integer(value) => Ok(Value::Integer(!value)),
+                _ => Err(RuntimeError::new(
                     expr.line_number,
-                    "Operands must be numbers.",
+                    "Operand must be an integer.",
                 )),
             },
-            BinaryOp::Equal => Ok(Value::Boolean(left == right)),
-            BinaryOp::NotEqual => Ok(Value::Boolean(left != right)),
         }
     }
 
+    fn binary(&mut self, expr: &Binary) -> RuntimeResult<Value> {
+        let left = self.evaluate(expr.left)?;
+        let right = self.evaluate(expr.right)?;
+        self.apply_binary(&expr.operator, left, right, expr.line_number)
+    }
+
     fn variable(&mut self, expr: &Variable) -> RuntimeResult<Value> {
         let env = self.environment.borrow();
         let result = env.get(&expr.name)?;
@@ -199,11 +1111,155 @@ impl ExprVisitor<RuntimeResult<Value>> for Interpreter {
     }
 
     fn assign(&mut self, expr: &Assign) -> RuntimeResult<Value> {
-        let value = self.evaluate(&expr.value)?;
+        let value = self.evaluate(expr.value)?;
 
         let mut env = self.environment.borrow_mut();
         env.assign(&expr.name, value.clone())?;
 
         Ok(value)
     }
+
+    fn compound_assign(&mut self, expr: &CompoundAssign) -> RuntimeResult<Value> {
+        let (_, new) = self.update(expr.target, expr.line_number, |interpreter, old| {
+            let value = interpreter.evaluate(expr.value)?;
+            interpreter.apply_binary(&expr.operator, old, value, expr.line_number)
+        })?;
+        Ok(new)
+    }
+
+    fn increment(&mut self, expr: &Increment) -> RuntimeResult<Value> {
+        let (old, new) = self.update(expr.target, expr.line_number, |interpreter, old| {
+            interpreter.apply_binary(&expr.operator, old, Value::Integer(1), expr.line_number)
+        })?;
+        Ok(if expr.prefix { new } else { old })
+    }
+
+    fn conditional(&mut self, expr: &Conditional) -> RuntimeResult<Value> {
+        if Self::is_truthy(&self.evaluate(expr.condition)?) {
+            self.evaluate(expr.then_branch)
+        } else {
+            self.evaluate(expr.else_branch)
+        }
+    }
+
+    fn call(&mut self, expr: &Call) -> RuntimeResult<Value> {
+        let callee = self.evaluate(expr.callee)?;
+        let arguments = expr
+            .arguments
+            .iter()
+            .map(|argument| self.evaluate(*argument))
+            .collect::<RuntimeResult<Vec<_>>>()?;
+        let named_arguments = expr
+            .named_arguments
+            .iter()
+            .map(|(name, argument)| Ok((name.clone(), self.evaluate(*argument)?)))
+            .collect::<RuntimeResult<Vec<_>>>()?;
+
+        self.call_value(&callee, arguments, named_arguments, expr.line_number)
+    }
+
+    fn lambda(&mut self, expr: &Lambda) -> RuntimeResult<Value> {
+        let function = Rc::new(Function {
+            ast: self.ast.clone(),
+            declaration: expr.function.clone(),
+            closure: self.environment.clone(),
+        });
+        heap::register_function(&function);
+        Ok(Value::Function(function))
+    }
+
+    fn list(&mut self, expr: &List) -> RuntimeResult<Value> {
+        let elements = expr
+            .elements
+            .iter()
+            .map(|element| self.evaluate(*element))
+            .collect::<RuntimeResult<Vec<_>>>()?;
+        self.allocate_values(elements.len(), expr.line_number)?;
+        Ok(Value::list(elements))
+    }
+
+    fn map(&mut self, expr: &Map) -> RuntimeResult<Value> {
+        self.allocate_values(2 * expr.entries.len(), expr.line_number)?;
+        let mut entries = OrderedMap::default();
+        for (key, value) in &expr.entries {
+            let key = natives::map::map_key(&self.evaluate(*key)?, expr.line_number)?;
+            let value = self.evaluate(*value)?;
+            entries.insert(key, value);
+        }
+        Ok(Value::map(entries))
+    }
+
+    fn get(&mut self, expr: &Get) -> RuntimeResult<Value> {
+        match self.evaluate(expr.object)? {
+            Value::Module(module) => module.get(&expr.name).ok_or_else(|| {
+                RuntimeError::new(
+                    expr.line_number,
+                    format!(
+                        "Module \"{}\" has no export \"{}\".",
+                        module.name, expr.name
+                    )
+                    .as_str(),
+                )
+            }),
+            _ => Err(RuntimeError::new(
+                expr.line_number,
+                "Only modules have members.",
+            )),
+        }
+    }
+
+    fn index(&mut self, expr: &Index) -> RuntimeResult<Value> {
+        let object = self.evaluate(expr.object)?;
+        let index = self.evaluate(expr.index)?;
+        Self::get_index(&object, &index, expr.line_number)
+    }
+
+    fn set_index(&mut self, expr: &SetIndex) -> RuntimeResult<Value> {
+        let object = self.evaluate(expr.object)?;
+        let index = self.evaluate(expr.index)?;
+        let value = self.evaluate(expr.value)?;
+        self.set_index_value(&object, &index, value, expr.line_number)
+    }
+}
+
+struct RenderedText<'a> {
+    interpreter: &'a mut Interpreter,
+    text: String,
+    line_number: usize,
+    // Why rendering stopped, since fmt::Error can't say
+    error: Option<RuntimeError>,
+}
+
+impl std::fmt::Write for RenderedText<'_> {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        let sandbox = &mut self.interpreter.sandbox;
+        let result = if self.text.len() + text.len() > natives::string::MAX_STRING_LENGTH {
+            Err(RuntimeError::new(
+                self.line_number,
+                "Value is too large to print.",
+            ))
+        } else {
+            sandbox
+                .tick(self.line_number)
+                .and_then(|()| sandbox.allocate(text.len(), self.line_number))
+        };
+
+        match result {
+            Ok(()) => {
+                self.text.push_str(text);
+                Ok(())
+            }
+            Err(err) => {
+                self.error = Some(err);
+                Err(std::fmt::Error)
+            }
+        }
+    }
+}
+
+// Roughly where the caller's frame is on the native stack
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
 }
@@ -0,0 +1,127 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::interner::Symbol;
+
+use super::{environment::Environment, value::Value};
+
+pub trait ModuleLoader {
+    // Turns an import path into the module's canonical id. Imports that resolve to the same
+    // id share a single loaded module.
+    fn resolve(&self, importer: Option<&str>, path: &str) -> Result<Arc<str>, String>;
+
+    fn load(&self, id: &str) -> Result<String, String>;
+
+    // Loaders that read files are refused when the interpreter's filesystem access is off
+    fn uses_filesystem(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Default)]
+pub struct FileSystemModuleLoader;
+
+impl ModuleLoader for FileSystemModuleLoader {
+    fn resolve(&self, importer: Option<&str>, path: &str) -> Result<Arc<str>, String> {
+        let path = relative_to(importer, path);
+        fs::canonicalize(&path)
+            .map(|path| path.to_string_lossy().into())
+            .map_err(|err| err.to_string())
+    }
+
+    fn load(&self, id: &str) -> Result<String, String> {
+        fs::read_to_string(id).map_err(|err| err.to_string())
+    }
+
+    fn uses_filesystem(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryModuleLoader {
+    sources: HashMap<String, String>,
+}
+
+impl MemoryModuleLoader {
+    pub fn insert(&mut self, path: &str, source: &str) {
+        self.sources
+            .insert(normalize(Path::new(path)), source.to_string());
+    }
+}
+
+impl ModuleLoader for MemoryModuleLoader {
+    fn resolve(&self, importer: Option<&str>, path: &str) -> Result<Arc<str>, String> {
+        let id = normalize(&relative_to(importer, path));
+        if self.sources.contains_key(&id) {
+            Ok(id.into())
+        } else {
+            Err("No such module.".to_string())
+        }
+    }
+
+    fn load(&self, id: &str) -> Result<String, String> {
+        self.sources
+            .get(id)
+            .cloned()
+            .ok_or_else(|| "No such module.".to_string())
+    }
+}
+
+fn relative_to(importer: Option<&str>, path: &str) -> PathBuf {
+    match importer.and_then(|importer| Path::new(importer).parent()) {
+        Some(directory) => directory.join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+fn normalize(path: &Path) -> String {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+pub struct Module {
+    pub name: Arc<str>,
+    environment: Rc<RefCell<Environment>>,
+    exports: HashSet<Symbol>,
+}
+
+impl Module {
+    pub fn new(
+        name: Arc<str>,
+        environment: Rc<RefCell<Environment>>,
+        exports: HashSet<Symbol>,
+    ) -> Self {
+        Self {
+            name,
+            environment,
+            exports,
+        }
+    }
+
+    pub fn get(&self, name: &Symbol) -> Option<Value> {
+        if !self.exports.contains(name) {
+            return None;
+        }
+        self.environment.borrow().lookup(name)
+    }
+
+    pub fn is_constant(&self, name: &Symbol) -> bool {
+        self.exports.contains(name) && self.environment.borrow().is_constant(name)
+    }
+}
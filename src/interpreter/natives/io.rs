@@ -0,0 +1,234 @@
+use std::{
+    fs,
+    io::{self, BufRead, Read, Write},
+    path::Path,
+};
+
+use crate::interpreter::{value::Value, Interpreter, RuntimeError, RuntimeResult};
+
+use super::{
+    string::{reserve_string, string_argument, MAX_STRING_LENGTH},
+    NativeFunction,
+};
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "input",
+        arity: 0..=1,
+        function: input,
+    },
+    NativeFunction {
+        name: "readLine",
+        arity: 0..=0,
+        function: read_line,
+    },
+    NativeFunction {
+        name: "eprint",
+        arity: 1..=1,
+        function: eprint,
+    },
+    NativeFunction {
+        name: "args",
+        arity: 0..=0,
+        function: args,
+    },
+    NativeFunction {
+        name: "readFile",
+        arity: 1..=1,
+        function: read_file,
+    },
+    NativeFunction {
+        name: "writeFile",
+        arity: 2..=2,
+        function: write_file,
+    },
+    NativeFunction {
+        name: "appendFile",
+        arity: 2..=2,
+        function: append_file,
+    },
+    NativeFunction {
+        name: "exists",
+        arity: 1..=1,
+        function: exists,
+    },
+    NativeFunction {
+        name: "listDir",
+        arity: 1..=1,
+        function: list_dir,
+    },
+];
+
+// Every filesystem native goes through here, so one flag on the interpreter turns them all off
+fn path_argument<'a>(
+    name: &str,
+    interpreter: &Interpreter,
+    arguments: &'a [Value],
+    line_number: usize,
+) -> RuntimeResult<&'a Path> {
+    if !interpreter.filesystem_access {
+        return Err(RuntimeError::new(
+            line_number,
+            format!("{}() is unavailable, filesystem access is disabled.", name).as_str(),
+        ));
+    }
+    let path = string_argument(name, arguments, 0, line_number)?;
+    Ok(Path::new(path.as_ref()))
+}
+
+fn io_error(name: &str, path: &Path, err: io::Error, line_number: usize) -> RuntimeError {
+    RuntimeError::new(
+        line_number,
+        format!("{}() failed for \"{}\": {}", name, path.display(), err).as_str(),
+    )
+}
+
+fn read_stdin_line(
+    name: &str,
+    interpreter: &mut Interpreter,
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let mut line = String::new();
+    // The length of a line is only known once it has been read, so it is charged afterwards
+    // and reading stops just past the ceiling
+    let read = io::stdin()
+        .lock()
+        .take(MAX_STRING_LENGTH as u64 + 1)
+        .read_line(&mut line)
+        .map_err(|err| {
+            RuntimeError::new(
+                line_number,
+                format!("{}() could not read from stdin: {}", name, err).as_str(),
+            )
+        })?;
+    if read == 0 {
+        return Ok(Value::Nil);
+    }
+
+    let trimmed = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(trimmed);
+    reserve_string(name, interpreter, line.len(), line_number)?;
+    Ok(Value::String(line.into()))
+}
+
+fn input(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    if let Some(prompt) = arguments.first() {
+        print!("{}", interpreter.render(prompt, line_number)?);
+        let _ = io::stdout().flush();
+    }
+    read_stdin_line("input", interpreter, line_number)
+}
+
+fn read_line(
+    interpreter: &mut Interpreter,
+    _: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    read_stdin_line("readLine", interpreter, line_number)
+}
+
+fn eprint(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    eprintln!("{}", interpreter.render(&arguments[0], line_number)?);
+    Ok(Value::Nil)
+}
+
+fn args(interpreter: &mut Interpreter, _: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    interpreter.allocate_values(interpreter.script_args.len(), line_number)?;
+    Ok(Value::list(
+        interpreter
+            .script_args
+            .iter()
+            .map(|arg| Value::String(arg.as_str().into()))
+            .collect(),
+    ))
+}
+
+fn read_file(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let path = path_argument("readFile", interpreter, arguments, line_number)?;
+    let size = fs::metadata(path)
+        .map_err(|err| io_error("readFile", path, err, line_number))?
+        .len();
+    reserve_string(
+        "readFile",
+        interpreter,
+        usize::try_from(size).unwrap_or(usize::MAX),
+        line_number,
+    )?;
+    let contents =
+        fs::read_to_string(path).map_err(|err| io_error("readFile", path, err, line_number))?;
+    Ok(Value::String(contents.into()))
+}
+
+fn write_file(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let path = path_argument("writeFile", interpreter, arguments, line_number)?;
+    let contents = string_argument("writeFile", arguments, 1, line_number)?;
+    fs::write(path, contents.as_bytes())
+        .map_err(|err| io_error("writeFile", path, err, line_number))?;
+    Ok(Value::Nil)
+}
+
+fn append_file(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let path = path_argument("appendFile", interpreter, arguments, line_number)?;
+    let contents = string_argument("appendFile", arguments, 1, line_number)?;
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|err| io_error("appendFile", path, err, line_number))?;
+    Ok(Value::Nil)
+}
+
+fn exists(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let path = path_argument("exists", interpreter, arguments, line_number)?;
+    Ok(Value::Boolean(path.exists()))
+}
+
+fn list_dir(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let path = path_argument("listDir", interpreter, arguments, line_number)?;
+    let mut names = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|err| io_error("listDir", path, err, line_number))?;
+    // Directory order is platform dependent, sorting keeps scripts deterministic
+    names.sort();
+
+    interpreter.allocate_values(names.len(), line_number)?;
+    Ok(Value::list(
+        names
+            .into_iter()
+            .map(|name| Value::String(name.into()))
+            .collect(),
+    ))
+}
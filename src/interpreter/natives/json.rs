@@ -0,0 +1,516 @@
+use std::{iter::Peekable, rc::Rc, str::Chars, sync::Arc};
+
+use crate::{
+    interpreter::{
+        map::{MapKey, OrderedMap},
+        value::Value,
+        Interpreter, RuntimeError, RuntimeResult,
+    },
+    lexer::format_float,
+};
+
+use super::{
+    string::{string_argument, MAX_STRING_LENGTH},
+    NativeFunction,
+};
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "json.parse",
+        arity: 1..=1,
+        function: parse,
+    },
+    NativeFunction {
+        name: "json.stringify",
+        arity: 1..=2,
+        function: stringify,
+    },
+];
+
+// Deeper documents are rejected instead of risking a native stack overflow
+const MAX_DEPTH: usize = 512;
+
+fn parse(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let source = string_argument("json.parse", arguments, 0, line_number)?;
+    let mut decoder = Decoder {
+        interpreter,
+        chars: source.chars().peekable(),
+        line: 1,
+        column: 1,
+        depth: 0,
+        line_number,
+    };
+
+    let value = decoder.value()?;
+    decoder.skip_whitespace();
+    if let Some(&char) = decoder.chars.peek() {
+        return Err(decoder.error(&format!("unexpected '{}' after JSON value", char)));
+    }
+    Ok(value)
+}
+
+struct Decoder<'a, 'i> {
+    interpreter: &'i mut Interpreter,
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    depth: usize,
+    line_number: usize,
+}
+
+impl Decoder<'_, '_> {
+    fn error(&self, message: &str) -> RuntimeError {
+        RuntimeError::new(
+            self.line_number,
+            format!(
+                "json.parse() {} at line {}, column {}.",
+                message, self.line, self.column
+            )
+            .as_str(),
+        )
+    }
+
+    fn unexpected(&mut self) -> RuntimeError {
+        match self.chars.peek() {
+            Some(&char) => self.error(&format!("unexpected '{}'", char)),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let char = self.chars.next()?;
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(char)
+    }
+
+    fn expect(&mut self, expected: char) -> RuntimeResult<()> {
+        if self.chars.peek() == Some(&expected) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.advance();
+        }
+    }
+
+    fn value(&mut self) -> RuntimeResult<Value> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => {
+                let string = self.string()?;
+                self.interpreter.allocate(string.len(), self.line_number)?;
+                Ok(Value::String(string.into()))
+            }
+            Some('t') => self.literal("true", Value::Boolean(true)),
+            Some('f') => self.literal("false", Value::Boolean(false)),
+            Some('n') => self.literal("null", Value::Nil),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> RuntimeResult<Value>) -> RuntimeResult<Value> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> RuntimeResult<Value> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn digits(&mut self, into: &mut String) -> RuntimeResult<()> {
+        if !self.chars.peek().is_some_and(char::is_ascii_digit) {
+            return Err(self.unexpected());
+        }
+        while let Some(digit) = self.chars.next_if(char::is_ascii_digit) {
+            self.column += 1;
+            into.push(digit);
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> RuntimeResult<Value> {
+        let mut number = String::new();
+        if self.chars.peek() == Some(&'-') {
+            self.advance();
+            number.push('-');
+        }
+
+        if self.chars.peek() == Some(&'0') {
+            self.advance();
+            number.push('0');
+        } else {
+            self.digits(&mut number)?;
+        }
+
+        let mut integral = true;
+        if self.chars.peek() == Some(&'.') {
+            self.advance();
+            number.push('.');
+            self.digits(&mut number)?;
+            integral = false;
+        }
+        if matches!(self.chars.peek(), Some('e' | 'E')) {
+            self.advance();
+            number.push('e');
+            if let Some(sign) = self.chars.next_if(|char| matches!(char, '+' | '-')) {
+                self.column += 1;
+                number.push(sign);
+            }
+            self.digits(&mut number)?;
+            integral = false;
+        }
+
+        // Integers too large for i64 degrade to floats, as other JSON readers do
+        match number.parse::<i64>() {
+            Ok(value) if integral => Ok(Value::Integer(value)),
+            _ => Ok(Value::Float(number.parse().unwrap_or(f64::NAN))),
+        }
+    }
+
+    fn string(&mut self) -> RuntimeResult<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+
+        loop {
+            match self.chars.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => {
+                    self.advance();
+                    return Ok(string);
+                }
+                Some('\\') => {
+                    self.advance();
+                    string.push(self.escape()?);
+                }
+                Some(char) if (*char as u32) < 0x20 => {
+                    return Err(self.error("control character in string"))
+                }
+                Some(_) => string.extend(self.advance()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> RuntimeResult<char> {
+        let escaped = match self.chars.peek() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                self.advance();
+                return self.unicode_escape();
+            }
+            _ => return Err(self.error("invalid escape sequence")),
+        };
+        self.advance();
+        Ok(escaped)
+    }
+
+    fn unicode_escape(&mut self) -> RuntimeResult<char> {
+        let high = self.hex_code_unit()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        // Characters outside the basic multilingual plane arrive as a surrogate pair
+        self.expect('\\')?;
+        self.expect('u')?;
+        let low = self.hex_code_unit()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("invalid unicode surrogate pair"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex_code_unit(&mut self) -> RuntimeResult<u32> {
+        let mut code_unit = 0;
+        for _ in 0..4 {
+            let Some(digit) = self.chars.peek().and_then(|char| char.to_digit(16)) else {
+                return Err(self.error("invalid unicode escape"));
+            };
+            self.advance();
+            code_unit = code_unit * 16 + digit;
+        }
+        Ok(code_unit)
+    }
+
+    fn array(&mut self) -> RuntimeResult<Value> {
+        self.expect('[')?;
+        let mut elements = vec![];
+
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&']') {
+            self.advance();
+            return Ok(Value::list(elements));
+        }
+
+        loop {
+            elements.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(',') => self.advance(),
+                Some(']') => break,
+                _ => return Err(self.unexpected()),
+            };
+        }
+        self.advance();
+
+        self.interpreter
+            .allocate_values(elements.len(), self.line_number)?;
+        Ok(Value::list(elements))
+    }
+
+    fn object(&mut self) -> RuntimeResult<Value> {
+        self.expect('{')?;
+        let mut entries = OrderedMap::default();
+
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.advance();
+            return Ok(Value::map(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.chars.peek() != Some(&'"') {
+                return Err(self.error("expected string key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+            self.interpreter.allocate(key.len(), self.line_number)?;
+            entries.insert(MapKey::String(key.into()), value);
+
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(',') => self.advance(),
+                Some('}') => break,
+                _ => return Err(self.unexpected()),
+            };
+        }
+        self.advance();
+
+        self.interpreter
+            .allocate_values(2 * entries.len(), self.line_number)?;
+        Ok(Value::map(entries))
+    }
+}
+
+fn stringify(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let indent = match arguments.get(1) {
+        None | Some(Value::Nil) => String::new(),
+        Some(Value::Integer(width)) if (0..=10).contains(width) => " ".repeat(*width as usize),
+        Some(Value::String(indent)) if indent.chars().count() <= 10 => indent.to_string(),
+        Some(_) => {
+            return Err(RuntimeError::new(
+                line_number,
+                "json.stringify() indent must be an integer from 0 to 10 or a string of at most 10 characters.",
+            ))
+        }
+    };
+
+    let mut encoder = Encoder {
+        interpreter,
+        indent,
+        output: String::new(),
+        stack: vec![],
+        values: 0,
+        line_number,
+    };
+    encoder.value(&arguments[0], 0)?;
+    Ok(Value::String(encoder.output.into()))
+}
+
+// Shared lists and maps are written out once for every place they appear, so a small value can
+// encode as a huge document
+const MAX_VALUES: usize = 1 << 24;
+
+// The output is charged to the sandbox as it is written rather than once it is complete
+struct Encoder<'i> {
+    interpreter: &'i mut Interpreter,
+    indent: String,
+    output: String,
+    // Lists and maps currently being encoded, used to detect cycles
+    stack: Vec<*const ()>,
+    values: usize,
+    line_number: usize,
+}
+
+impl Encoder<'_> {
+    fn error(&self, message: &str) -> RuntimeError {
+        RuntimeError::new(
+            self.line_number,
+            format!("json.stringify() {}.", message).as_str(),
+        )
+    }
+
+    fn write(&mut self, text: &str) -> RuntimeResult<()> {
+        if self.output.len() + text.len() > MAX_STRING_LENGTH {
+            return Err(self.error("result is too large"));
+        }
+        let sandbox = &mut self.interpreter.sandbox;
+        sandbox.tick(self.line_number)?;
+        sandbox.allocate(text.len(), self.line_number)?;
+        self.output.push_str(text);
+        Ok(())
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) -> RuntimeResult<()> {
+        self.values += 1;
+        if self.values > MAX_VALUES {
+            return Err(self.error("result is too large"));
+        }
+
+        match value {
+            Value::Nil => self.write("null")?,
+            Value::Boolean(value) => self.write(&value.to_string())?,
+            Value::Integer(value) => self.write(&value.to_string())?,
+            Value::Float(value) if value.is_finite() => self.write(&format_float(*value))?,
+            Value::Float(value) => return Err(self.error(&format!("can't encode {}", value))),
+            Value::String(string) => self.string(string)?,
+            Value::List(list) => {
+                self.enter(Rc::as_ptr(list) as *const ())?;
+                let elements = list.borrow().clone();
+                self.write("[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        self.write(",")?;
+                    }
+                    self.newline(depth + 1)?;
+                    self.value(element, depth + 1)?;
+                }
+                if !elements.is_empty() {
+                    self.newline(depth)?;
+                }
+                self.write("]")?;
+                self.stack.pop();
+            }
+            Value::Map(map) => {
+                self.enter(Rc::as_ptr(map) as *const ())?;
+                let to_json = map
+                    .borrow()
+                    .get(&MapKey::String(Arc::from("toJSON")))
+                    .cloned();
+                match to_json {
+                    Some(callee @ (Value::Function(_) | Value::NativeFunction(_))) => {
+                        let replacement = self.interpreter.call_value(
+                            &callee,
+                            vec![],
+                            vec![],
+                            self.line_number,
+                        )?;
+                        self.value(&replacement, depth)?;
+                    }
+                    _ => self.map(&map.borrow().clone(), depth)?,
+                }
+                self.stack.pop();
+            }
+            Value::Range(_) | Value::Function(_) | Value::NativeFunction(_) | Value::Module(_) => {
+                return Err(self.error(&format!("can't encode {}", value)))
+            }
+        }
+        Ok(())
+    }
+
+    fn map(&mut self, map: &OrderedMap, depth: usize) -> RuntimeResult<()> {
+        self.write("{")?;
+        for (index, (key, value)) in map.iter().enumerate() {
+            if index > 0 {
+                self.write(",")?;
+            }
+            self.newline(depth + 1)?;
+            // Object keys must be strings, so other keys use their JSON spelling
+            match key {
+                MapKey::String(key) => self.string(key)?,
+                MapKey::Nil => self.string("null")?,
+                key => self.string(&Value::from(key).to_string())?,
+            }
+            self.write(if self.indent.is_empty() { ":" } else { ": " })?;
+            self.value(value, depth + 1)?;
+        }
+        if !map.is_empty() {
+            self.newline(depth)?;
+        }
+        self.write("}")
+    }
+
+    // The stack also counts maps replaced through toJSON, so its length is the real nesting depth
+    fn enter(&mut self, pointer: *const ()) -> RuntimeResult<()> {
+        if self.stack.len() == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        if self.stack.contains(&pointer) {
+            return Err(self.error("can't encode a cyclic structure"));
+        }
+        self.stack.push(pointer);
+        Ok(())
+    }
+
+    fn newline(&mut self, depth: usize) -> RuntimeResult<()> {
+        if self.indent.is_empty() {
+            return Ok(());
+        }
+        let mut newline = String::from("\n");
+        for _ in 0..depth {
+            newline.push_str(&self.indent);
+        }
+        self.write(&newline)
+    }
+
+    fn string(&mut self, string: &str) -> RuntimeResult<()> {
+        let mut escaped = String::with_capacity(string.len() + 2);
+        escaped.push('"');
+        for char in string.chars() {
+            match char {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                '\u{8}' => escaped.push_str("\\b"),
+                '\u{c}' => escaped.push_str("\\f"),
+                char if (char as u32) < 0x20 => {
+                    escaped.push_str(&format!("\\u{:04x}", char as u32))
+                }
+                char => escaped.push(char),
+            }
+        }
+        escaped.push('"');
+        self.write(&escaped)
+    }
+}
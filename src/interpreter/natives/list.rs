@@ -0,0 +1,186 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::interpreter::{range::Range, value::Value, Interpreter, RuntimeError, RuntimeResult};
+
+use super::NativeFunction;
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "len",
+        arity: 1..=1,
+        function: len,
+    },
+    NativeFunction {
+        name: "push",
+        arity: 2..=2,
+        function: push,
+    },
+    NativeFunction {
+        name: "pop",
+        arity: 1..=1,
+        function: pop,
+    },
+    NativeFunction {
+        name: "insert",
+        arity: 3..=3,
+        function: insert,
+    },
+    NativeFunction {
+        name: "slice",
+        arity: 3..=3,
+        function: slice,
+    },
+    NativeFunction {
+        name: "range",
+        arity: 2..=3,
+        function: range,
+    },
+];
+
+pub(crate) fn element_index(index: &Value, len: usize, line_number: usize) -> RuntimeResult<usize> {
+    match index {
+        Value::Integer(value) if *value >= 0 && (*value as usize) < len => Ok(*value as usize),
+        Value::Integer(_) => Err(RuntimeError::new(line_number, "List index out of range.")),
+        _ => Err(RuntimeError::new(
+            line_number,
+            "List index must be an integer.",
+        )),
+    }
+}
+
+fn list_argument<'a>(
+    name: &str,
+    value: &'a Value,
+    line_number: usize,
+) -> RuntimeResult<&'a Rc<RefCell<Vec<Value>>>> {
+    match value {
+        Value::List(list) => Ok(list),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!("{}() expects a list as its first argument.", name).as_str(),
+        )),
+    }
+}
+
+pub(super) fn bound_argument(
+    name: &str,
+    value: &Value,
+    len: usize,
+    line_number: usize,
+) -> RuntimeResult<usize> {
+    match value {
+        Value::Integer(value) if *value >= 0 && *value as usize <= len => Ok(*value as usize),
+        Value::Integer(_) => Err(RuntimeError::new(
+            line_number,
+            format!("{}() index out of range.", name).as_str(),
+        )),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!("{}() expects an integer as index.", name).as_str(),
+        )),
+    }
+}
+
+fn len(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    match &arguments[0] {
+        Value::List(list) => Ok(Value::Integer(list.borrow().len() as i64)),
+        Value::Map(map) => Ok(Value::Integer(map.borrow().len() as i64)),
+        Value::String(string) => Ok(Value::Integer(string.chars().count() as i64)),
+        Value::Range(range) => Ok(Value::Integer(
+            i64::try_from(range.len()).unwrap_or(i64::MAX),
+        )),
+        _ => Err(RuntimeError::new(
+            line_number,
+            "len() expects a list, a map, a string or a range.",
+        )),
+    }
+}
+
+fn push(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let list = list_argument("push", &arguments[0], line_number)?;
+    interpreter.allocate_values(1, line_number)?;
+    list.borrow_mut().push(arguments[1].clone());
+    Ok(Value::Nil)
+}
+
+fn pop(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let list = list_argument("pop", &arguments[0], line_number)?;
+    let value = list.borrow_mut().pop();
+    value.ok_or_else(|| RuntimeError::new(line_number, "pop() from empty list."))
+}
+
+fn insert(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let list = list_argument("insert", &arguments[0], line_number)?;
+    let len = list.borrow().len();
+    let index = bound_argument("insert", &arguments[1], len, line_number)?;
+    interpreter.allocate_values(1, line_number)?;
+    list.borrow_mut().insert(index, arguments[2].clone());
+    Ok(Value::Nil)
+}
+
+fn slice(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let list = list_argument("slice", &arguments[0], line_number)?.borrow();
+    let start = bound_argument("slice", &arguments[1], list.len(), line_number)?;
+    let end = bound_argument("slice", &arguments[2], list.len(), line_number)?;
+    if start > end {
+        return Err(RuntimeError::new(
+            line_number,
+            "slice() start must not be greater than end.",
+        ));
+    }
+    interpreter.allocate_values(end - start, line_number)?;
+    Ok(Value::list(list[start..end].to_vec()))
+}
+
+// Ranges are lazy, so their length never needs to be allocated up front
+fn range(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let step = arguments.get(2).cloned().unwrap_or(Value::Integer(1));
+
+    match (&arguments[0], &arguments[1], &step) {
+        (Value::Integer(start), Value::Integer(end), Value::Integer(step)) => {
+            if *step == 0 {
+                return Err(RuntimeError::new(
+                    line_number,
+                    "range() step must not be zero.",
+                ));
+            }
+            Ok(Value::Range(Range::Integers {
+                start: *start,
+                end: *end,
+                step: *step,
+            }))
+        }
+        (start, end, step) => {
+            let [start, end, step] = [start, end, step].map(|bound| match bound {
+                Value::Integer(value) => Some(*value as f64),
+                Value::Float(value) => Some(*value),
+                _ => None,
+            });
+            let (Some(start), Some(end), Some(step)) = (start, end, step) else {
+                return Err(RuntimeError::new(
+                    line_number,
+                    "range() expects numbers as arguments.",
+                ));
+            };
+            if step == 0.0 {
+                return Err(RuntimeError::new(
+                    line_number,
+                    "range() step must not be zero.",
+                ));
+            }
+            Ok(Value::Range(Range::Floats { start, end, step }))
+        }
+    }
+}
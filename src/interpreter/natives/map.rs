@@ -0,0 +1,94 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::interpreter::{
+    map::{MapKey, OrderedMap},
+    value::Value,
+    Interpreter, RuntimeError, RuntimeResult,
+};
+
+use super::NativeFunction;
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "has",
+        arity: 2..=2,
+        function: has,
+    },
+    NativeFunction {
+        name: "remove",
+        arity: 2..=2,
+        function: remove,
+    },
+    NativeFunction {
+        name: "keys",
+        arity: 1..=1,
+        function: keys,
+    },
+    NativeFunction {
+        name: "values",
+        arity: 1..=1,
+        function: values,
+    },
+];
+
+pub(crate) fn map_key(key: &Value, line_number: usize) -> RuntimeResult<MapKey> {
+    MapKey::try_from(key).map_err(|_| {
+        RuntimeError::new(
+            line_number,
+            "Map keys must be strings, numbers, booleans or nil.",
+        )
+    })
+}
+
+fn map_argument<'a>(
+    name: &str,
+    value: &'a Value,
+    line_number: usize,
+) -> RuntimeResult<&'a Rc<RefCell<OrderedMap>>> {
+    match value {
+        Value::Map(map) => Ok(map),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!("{}() expects a map as its first argument.", name).as_str(),
+        )),
+    }
+}
+
+fn has(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let map = map_argument("has", &arguments[0], line_number)?;
+    let key = map_key(&arguments[1], line_number)?;
+    Ok(Value::Boolean(map.borrow().contains_key(&key)))
+}
+
+fn remove(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let map = map_argument("remove", &arguments[0], line_number)?;
+    let key = map_key(&arguments[1], line_number)?;
+    let removed = map.borrow_mut().remove(&key);
+    Ok(removed.unwrap_or(Value::Nil))
+}
+
+fn keys(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let map = map_argument("keys", &arguments[0], line_number)?;
+    interpreter.allocate_values(map.borrow().len(), line_number)?;
+    let keys = map.borrow().iter().map(|(key, _)| key.into()).collect();
+    Ok(Value::list(keys))
+}
+
+fn values(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let map = map_argument("values", &arguments[0], line_number)?;
+    interpreter.allocate_values(map.borrow().len(), line_number)?;
+    let values = map
+        .borrow()
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect();
+    Ok(Value::list(values))
+}
@@ -0,0 +1,388 @@
+use std::{
+    cmp::Ordering,
+    f64::consts::PI,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    interpreter::{
+        value::{NumberPair, Value},
+        Interpreter, RuntimeError, RuntimeResult,
+    },
+    parser::BinaryOp,
+};
+
+use super::{NativeFunction, ORDINALS};
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "sqrt",
+        arity: 1..=1,
+        function: sqrt,
+    },
+    NativeFunction {
+        name: "pow",
+        arity: 2..=2,
+        function: pow,
+    },
+    NativeFunction {
+        name: "abs",
+        arity: 1..=1,
+        function: abs,
+    },
+    NativeFunction {
+        name: "floor",
+        arity: 1..=1,
+        function: floor,
+    },
+    NativeFunction {
+        name: "ceil",
+        arity: 1..=1,
+        function: ceil,
+    },
+    NativeFunction {
+        name: "round",
+        arity: 1..=1,
+        function: round,
+    },
+    NativeFunction {
+        name: "min",
+        arity: 1..=usize::MAX,
+        function: min,
+    },
+    NativeFunction {
+        name: "max",
+        arity: 1..=usize::MAX,
+        function: max,
+    },
+    NativeFunction {
+        name: "sin",
+        arity: 1..=1,
+        function: sin,
+    },
+    NativeFunction {
+        name: "cos",
+        arity: 1..=1,
+        function: cos,
+    },
+    NativeFunction {
+        name: "tan",
+        arity: 1..=1,
+        function: tan,
+    },
+    NativeFunction {
+        name: "atan2",
+        arity: 2..=2,
+        function: atan2,
+    },
+    NativeFunction {
+        name: "log",
+        arity: 1..=2,
+        function: log,
+    },
+    NativeFunction {
+        name: "exp",
+        arity: 1..=1,
+        function: exp,
+    },
+    NativeFunction {
+        name: "isNan",
+        arity: 1..=1,
+        function: is_nan,
+    },
+    NativeFunction {
+        name: "isInfinite",
+        arity: 1..=1,
+        function: is_infinite,
+    },
+    NativeFunction {
+        name: "random",
+        arity: 0..=0,
+        function: random,
+    },
+    NativeFunction {
+        name: "randomInt",
+        arity: 2..=2,
+        function: random_int,
+    },
+    NativeFunction {
+        name: "seed",
+        arity: 1..=1,
+        function: seed,
+    },
+];
+
+pub(super) const CONSTANTS: &[(&str, f64)] =
+    &[("pi", PI), ("inf", f64::INFINITY), ("nan", f64::NAN)];
+
+// SplitMix64, which is small, fast and fully determined by its seed
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn number_argument(
+    name: &str,
+    arguments: &[Value],
+    position: usize,
+    line_number: usize,
+) -> RuntimeResult<f64> {
+    match arguments[position] {
+        Value::Integer(value) => Ok(value as f64),
+        Value::Float(value) => Ok(value),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!(
+                "{}() expects a number as its {} argument.",
+                name, ORDINALS[position]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn integer_argument(
+    name: &str,
+    arguments: &[Value],
+    position: usize,
+    line_number: usize,
+) -> RuntimeResult<i64> {
+    match arguments[position] {
+        Value::Integer(value) => Ok(value),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!(
+                "{}() expects an integer as its {} argument.",
+                name, ORDINALS[position]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn float_function(
+    name: &str,
+    arguments: &[Value],
+    line_number: usize,
+    function: fn(f64) -> f64,
+) -> RuntimeResult<Value> {
+    let value = number_argument(name, arguments, 0, line_number)?;
+    Ok(Value::Float(function(value)))
+}
+
+// Integers pass through unchanged; floats become integers when the result fits
+fn rounding_function(
+    name: &str,
+    arguments: &[Value],
+    line_number: usize,
+    function: fn(f64) -> f64,
+) -> RuntimeResult<Value> {
+    match arguments[0] {
+        Value::Integer(value) => Ok(Value::Integer(value)),
+        Value::Float(value) => {
+            let rounded = function(value);
+            if rounded >= i64::MIN as f64 && rounded < i64::MAX as f64 {
+                Ok(Value::Integer(rounded as i64))
+            } else {
+                Ok(Value::Float(rounded))
+            }
+        }
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!("{}() expects a number as its first argument.", name).as_str(),
+        )),
+    }
+}
+
+fn extremum(
+    name: &str,
+    arguments: &[Value],
+    line_number: usize,
+    wanted: Ordering,
+) -> RuntimeResult<Value> {
+    let values = match arguments {
+        [Value::List(list)] => list.borrow().clone(),
+        _ => arguments.to_vec(),
+    };
+
+    let mut values = values.into_iter();
+    let Some(mut best) = values.next() else {
+        return Err(RuntimeError::new(
+            line_number,
+            format!("{}() of an empty list.", name).as_str(),
+        ));
+    };
+
+    for value in std::iter::once(best.clone()).chain(values) {
+        match NumberPair::try_from((&value, &best)).map(|pair| pair.compare()) {
+            Ok(Some(ordering)) if ordering == wanted => best = value,
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(Value::Float(f64::NAN)),
+            Err(_) => {
+                return Err(RuntimeError::new(
+                    line_number,
+                    format!("{}() expects numbers as arguments.", name).as_str(),
+                ))
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+fn sqrt(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    float_function("sqrt", arguments, line_number, f64::sqrt)
+}
+
+fn pow(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    number_argument("pow", arguments, 0, line_number)?;
+    number_argument("pow", arguments, 1, line_number)?;
+    interpreter.apply_binary(
+        &BinaryOp::Pow,
+        arguments[0].clone(),
+        arguments[1].clone(),
+        line_number,
+    )
+}
+
+fn abs(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    match arguments[0] {
+        Value::Integer(value) => value
+            .checked_abs()
+            .map(Value::Integer)
+            .ok_or_else(|| RuntimeError::new(line_number, "Integer overflow.")),
+        Value::Float(value) => Ok(Value::Float(value.abs())),
+        _ => Err(RuntimeError::new(
+            line_number,
+            "abs() expects a number as its first argument.",
+        )),
+    }
+}
+
+fn floor(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    rounding_function("floor", arguments, line_number, f64::floor)
+}
+
+fn ceil(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    rounding_function("ceil", arguments, line_number, f64::ceil)
+}
+
+fn round(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    rounding_function("round", arguments, line_number, f64::round)
+}
+
+fn min(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    extremum("min", arguments, line_number, Ordering::Less)
+}
+
+fn max(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    extremum("max", arguments, line_number, Ordering::Greater)
+}
+
+fn sin(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    float_function("sin", arguments, line_number, f64::sin)
+}
+
+fn cos(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    float_function("cos", arguments, line_number, f64::cos)
+}
+
+fn tan(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    float_function("tan", arguments, line_number, f64::tan)
+}
+
+fn atan2(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let y = number_argument("atan2", arguments, 0, line_number)?;
+    let x = number_argument("atan2", arguments, 1, line_number)?;
+    Ok(Value::Float(y.atan2(x)))
+}
+
+fn log(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let value = number_argument("log", arguments, 0, line_number)?;
+    if arguments.len() == 1 {
+        return Ok(Value::Float(value.ln()));
+    }
+    let base = number_argument("log", arguments, 1, line_number)?;
+    Ok(Value::Float(value.log(base)))
+}
+
+fn exp(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    float_function("exp", arguments, line_number, f64::exp)
+}
+
+fn is_nan(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let value = number_argument("isNan", arguments, 0, line_number)?;
+    Ok(Value::Boolean(value.is_nan()))
+}
+
+fn is_infinite(
+    _: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let value = number_argument("isInfinite", arguments, 0, line_number)?;
+    Ok(Value::Boolean(value.is_infinite()))
+}
+
+fn random(interpreter: &mut Interpreter, _: &[Value], _: usize) -> RuntimeResult<Value> {
+    Ok(Value::Float(interpreter.random.next_f64()))
+}
+
+fn random_int(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let low = integer_argument("randomInt", arguments, 0, line_number)?;
+    let high = integer_argument("randomInt", arguments, 1, line_number)?;
+    if low > high {
+        return Err(RuntimeError::new(
+            line_number,
+            "randomInt() lower bound must not be greater than upper bound.",
+        ));
+    }
+
+    // Both bounds are inclusive, so the span can be as large as 2^64
+    let span = (high as i128 - low as i128 + 1) as u128;
+    let offset = (interpreter.random.next_u64() as u128 * span) >> 64;
+    Ok(Value::Integer((low as i128 + offset as i128) as i64))
+}
+
+fn seed(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let seed = integer_argument("seed", arguments, 0, line_number)?;
+    interpreter.random = Random::new(seed as u64);
+    Ok(Value::Nil)
+}
@@ -0,0 +1,130 @@
+pub mod io;
+pub mod json;
+pub mod list;
+pub mod map;
+pub mod math;
+pub mod string;
+
+use std::{collections::HashSet, ops::RangeInclusive, rc::Rc};
+
+use crate::interner::Interner;
+
+use super::{
+    environment::Environment, module::Module, value::Value, Interpreter, RuntimeError,
+    RuntimeResult,
+};
+
+pub(super) const ORDINALS: [&str; 3] = ["first", "second", "third"];
+
+pub type NativeFn = fn(&mut Interpreter, &[Value], usize) -> RuntimeResult<Value>;
+
+#[derive(Clone)]
+pub struct NativeFunction {
+    pub name: &'static str,
+    pub arity: RangeInclusive<usize>,
+    pub function: NativeFn,
+}
+
+impl NativeFunction {
+    pub fn check_arity(&self, argument_count: usize, line_number: usize) -> RuntimeResult<()> {
+        if self.arity.contains(&argument_count) {
+            return Ok(());
+        }
+
+        let expected = if self.arity.start() == self.arity.end() {
+            self.arity.start().to_string()
+        } else if *self.arity.end() == usize::MAX {
+            format!("at least {}", self.arity.start())
+        } else {
+            format!("{} to {}", self.arity.start(), self.arity.end())
+        };
+
+        Err(RuntimeError::new(
+            line_number,
+            format!(
+                "{}() expected {} arguments but got {}.",
+                self.name, expected, argument_count
+            )
+            .as_str(),
+        ))
+    }
+
+    pub fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: &[Value],
+        line_number: usize,
+    ) -> RuntimeResult<Value> {
+        (self.function)(interpreter, arguments, line_number)
+    }
+}
+
+pub(super) fn define_globals(environment: &mut Environment, interner: &mut Interner) {
+    for native in list::NATIVES
+        .iter()
+        .chain(map::NATIVES)
+        .chain(string::NATIVES)
+        .chain(math::NATIVES)
+        .chain(io::NATIVES)
+    {
+        define_native(environment, interner, native);
+    }
+    define_constants(environment, interner, math::CONSTANTS);
+
+    // The math natives are also reachable through a namespace, as in math.sqrt(2)
+    define_namespace(
+        environment,
+        interner,
+        "math",
+        math::NATIVES,
+        math::CONSTANTS,
+    );
+    define_namespace(environment, interner, "json", json::NATIVES, &[]);
+}
+
+// Natives are bound by the last segment of their name, so "json.parse" becomes json.parse
+fn define_namespace(
+    environment: &mut Environment,
+    interner: &mut Interner,
+    name: &str,
+    natives: &[NativeFunction],
+    constants: &[(&str, f64)],
+) {
+    let namespace = Environment::new(None);
+    for native in natives {
+        define_native(&mut namespace.borrow_mut(), interner, native);
+    }
+    define_constants(&mut namespace.borrow_mut(), interner, constants);
+
+    let exports = natives
+        .iter()
+        .map(|native| binding_name(native.name))
+        .chain(constants.iter().map(|(name, _)| *name))
+        .map(|name| interner.symbol(name))
+        .collect::<HashSet<_>>();
+    environment.define(
+        interner.symbol(name),
+        Value::Module(Rc::new(Module::new(name.into(), namespace, exports))),
+    );
+}
+
+fn binding_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+fn define_native(environment: &mut Environment, interner: &mut Interner, native: &NativeFunction) {
+    environment.define(
+        interner.symbol(binding_name(native.name)),
+        Value::NativeFunction(Rc::new(native.clone())),
+    );
+}
+
+fn define_constants(
+    environment: &mut Environment,
+    interner: &mut Interner,
+    constants: &[(&str, f64)],
+) {
+    for (name, value) in constants {
+        environment.define_constant(interner.symbol(name), Value::Float(*value));
+    }
+}
@@ -0,0 +1,344 @@
+use std::sync::Arc;
+
+use crate::interpreter::{value::Value, Interpreter, RuntimeError, RuntimeResult};
+
+use super::{list::bound_argument, NativeFunction, ORDINALS};
+
+// A hard ceiling on strings built by natives, so a huge result fails even without a memory limit
+pub(crate) const MAX_STRING_LENGTH: usize = 1 << 30;
+
+pub(super) const NATIVES: &[NativeFunction] = &[
+    NativeFunction {
+        name: "substring",
+        arity: 2..=3,
+        function: substring,
+    },
+    NativeFunction {
+        name: "indexOf",
+        arity: 2..=2,
+        function: index_of,
+    },
+    NativeFunction {
+        name: "split",
+        arity: 2..=2,
+        function: split,
+    },
+    NativeFunction {
+        name: "join",
+        arity: 2..=2,
+        function: join,
+    },
+    NativeFunction {
+        name: "trim",
+        arity: 1..=1,
+        function: trim,
+    },
+    NativeFunction {
+        name: "upper",
+        arity: 1..=1,
+        function: upper,
+    },
+    NativeFunction {
+        name: "lower",
+        arity: 1..=1,
+        function: lower,
+    },
+    NativeFunction {
+        name: "replace",
+        arity: 3..=3,
+        function: replace,
+    },
+    NativeFunction {
+        name: "startsWith",
+        arity: 2..=2,
+        function: starts_with,
+    },
+    NativeFunction {
+        name: "endsWith",
+        arity: 2..=2,
+        function: ends_with,
+    },
+    NativeFunction {
+        name: "chars",
+        arity: 1..=1,
+        function: chars,
+    },
+    NativeFunction {
+        name: "repeat",
+        arity: 2..=2,
+        function: repeat,
+    },
+];
+
+pub(super) fn string_argument<'a>(
+    name: &str,
+    arguments: &'a [Value],
+    position: usize,
+    line_number: usize,
+) -> RuntimeResult<&'a Arc<str>> {
+    match &arguments[position] {
+        Value::String(string) => Ok(string),
+        _ => Err(RuntimeError::new(
+            line_number,
+            format!(
+                "{}() expects a string as its {} argument.",
+                name, ORDINALS[position]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+// Charges a string of `len` bytes before it is built, so a limit stops the allocation itself
+pub(super) fn reserve_string(
+    name: &str,
+    interpreter: &mut Interpreter,
+    len: usize,
+    line_number: usize,
+) -> RuntimeResult<()> {
+    if len > MAX_STRING_LENGTH {
+        return Err(RuntimeError::new(
+            line_number,
+            format!("{}() result is too large.", name).as_str(),
+        ));
+    }
+    interpreter.allocate(len, line_number)
+}
+
+fn new_string_list(
+    interpreter: &mut Interpreter,
+    strings: Vec<&str>,
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    interpreter.allocate_values(strings.len(), line_number)?;
+    interpreter.allocate(strings.iter().map(|string| string.len()).sum(), line_number)?;
+    Ok(Value::list(
+        strings
+            .into_iter()
+            .map(|string| Value::String(string.into()))
+            .collect(),
+    ))
+}
+
+fn char_slices(string: &str) -> Vec<&str> {
+    string
+        .char_indices()
+        .map(|(index, char)| &string[index..index + char.len_utf8()])
+        .collect()
+}
+
+fn substring(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("substring", arguments, 0, line_number)?;
+    let len = string.chars().count();
+    let start = bound_argument("substring", &arguments[1], len, line_number)?;
+    let end = match arguments.get(2) {
+        Some(end) => bound_argument("substring", end, len, line_number)?,
+        None => len,
+    };
+    if start > end {
+        return Err(RuntimeError::new(
+            line_number,
+            "substring() start must not be greater than end.",
+        ));
+    }
+
+    let byte_index = |index: usize| {
+        string
+            .char_indices()
+            .nth(index)
+            .map_or(string.len(), |(byte_index, _)| byte_index)
+    };
+    let substring = &string[byte_index(start)..byte_index(end)];
+    reserve_string("substring", interpreter, substring.len(), line_number)?;
+    Ok(Value::String(substring.into()))
+}
+
+fn index_of(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let string = string_argument("indexOf", arguments, 0, line_number)?;
+    let needle = string_argument("indexOf", arguments, 1, line_number)?;
+
+    Ok(Value::Integer(match string.find(needle.as_ref()) {
+        Some(byte_index) => string[..byte_index].chars().count() as i64,
+        None => -1,
+    }))
+}
+
+fn split(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("split", arguments, 0, line_number)?;
+    let separator = string_argument("split", arguments, 1, line_number)?;
+
+    // An empty separator splits between characters rather than around empty matches
+    let parts = if separator.is_empty() {
+        char_slices(string)
+    } else {
+        string.split(separator.as_ref()).collect()
+    };
+    new_string_list(interpreter, parts, line_number)
+}
+
+fn join(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let Value::List(list) = &arguments[0] else {
+        return Err(RuntimeError::new(
+            line_number,
+            "join() expects a list as its first argument.",
+        ));
+    };
+    let separator = string_argument("join", arguments, 1, line_number)?;
+
+    let parts = list
+        .borrow()
+        .iter()
+        .map(|value| match value {
+            Value::String(string) => Ok(string.clone()),
+            _ => Err(RuntimeError::new(
+                line_number,
+                "join() expects a list of strings.",
+            )),
+        })
+        .collect::<RuntimeResult<Vec<_>>>()?;
+
+    let len = parts
+        .iter()
+        .map(|part| part.len())
+        .fold(0, usize::saturating_add)
+        .saturating_add(
+            separator
+                .len()
+                .saturating_mul(parts.len().saturating_sub(1)),
+        );
+    reserve_string("join", interpreter, len, line_number)?;
+    Ok(Value::String(parts.join(separator.as_ref()).into()))
+}
+
+fn trim(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("trim", arguments, 0, line_number)?;
+    let trimmed = string.trim();
+    reserve_string("trim", interpreter, trimmed.len(), line_number)?;
+    Ok(Value::String(trimmed.into()))
+}
+
+fn upper(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("upper", arguments, 0, line_number)?;
+    let len = string
+        .chars()
+        .flat_map(char::to_uppercase)
+        .map(char::len_utf8)
+        .sum();
+    reserve_string("upper", interpreter, len, line_number)?;
+    Ok(Value::String(string.to_uppercase().into()))
+}
+
+fn lower(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("lower", arguments, 0, line_number)?;
+    // Slightly over for a final sigma, which to_lowercase maps by context to a shorter form
+    let len = string
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(char::len_utf8)
+        .sum();
+    reserve_string("lower", interpreter, len, line_number)?;
+    Ok(Value::String(string.to_lowercase().into()))
+}
+
+fn replace(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("replace", arguments, 0, line_number)?;
+    let from = string_argument("replace", arguments, 1, line_number)?;
+    let to = string_argument("replace", arguments, 2, line_number)?;
+    if from.is_empty() {
+        return Err(RuntimeError::new(
+            line_number,
+            "replace() pattern must not be empty.",
+        ));
+    }
+
+    let count = string.matches(from.as_ref()).count();
+    reserve_string(
+        "replace",
+        interpreter,
+        (string.len() - count * from.len()).saturating_add(count.saturating_mul(to.len())),
+        line_number,
+    )?;
+    Ok(Value::String(
+        string.replace(from.as_ref(), to.as_ref()).into(),
+    ))
+}
+
+fn starts_with(
+    _: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("startsWith", arguments, 0, line_number)?;
+    let prefix = string_argument("startsWith", arguments, 1, line_number)?;
+    Ok(Value::Boolean(string.starts_with(prefix.as_ref())))
+}
+
+fn ends_with(_: &mut Interpreter, arguments: &[Value], line_number: usize) -> RuntimeResult<Value> {
+    let string = string_argument("endsWith", arguments, 0, line_number)?;
+    let suffix = string_argument("endsWith", arguments, 1, line_number)?;
+    Ok(Value::Boolean(string.ends_with(suffix.as_ref())))
+}
+
+fn chars(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("chars", arguments, 0, line_number)?;
+    new_string_list(interpreter, char_slices(string), line_number)
+}
+
+fn repeat(
+    interpreter: &mut Interpreter,
+    arguments: &[Value],
+    line_number: usize,
+) -> RuntimeResult<Value> {
+    let string = string_argument("repeat", arguments, 0, line_number)?;
+    let count = match arguments[1] {
+        Value::Integer(count) if count >= 0 => count as usize,
+        Value::Integer(_) => {
+            return Err(RuntimeError::new(
+                line_number,
+                "repeat() count must not be negative.",
+            ))
+        }
+        _ => {
+            return Err(RuntimeError::new(
+                line_number,
+                "repeat() expects an integer as its second argument.",
+            ))
+        }
+    };
+
+    let size = string.len().saturating_mul(count);
+    reserve_string("repeat", interpreter, size, line_number)?;
+    Ok(Value::String(string.repeat(count).into()))
+}
@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use crate::lexer::format_float;
+
+use super::value::Value;
+
+// Only the bounds are stored and elements are computed on demand, so a range of any length
+// costs the same until it is iterated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Range {
+    Integers { start: i64, end: i64, step: i64 },
+    Floats { start: f64, end: f64, step: f64 },
+}
+
+impl Range {
+    pub fn len(&self) -> u64 {
+        match *self {
+            Self::Integers { start, end, step } => {
+                // Both bounds are i64, so the distance and the count need more room than that
+                let (start, end, step) = (start as i128, end as i128, step as i128);
+                let distance = if step > 0 { end - start } else { start - end };
+                ((distance.max(0) + step.abs() - 1) / step.abs()) as u64
+            }
+            Self::Floats { start, end, step } => ((end - start) / step).ceil().max(0.0) as u64,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: u64) -> Option<Value> {
+        (index < self.len()).then(|| self.element(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Value> + '_ {
+        (0..self.len()).map(|index| self.element(index))
+    }
+
+    fn element(&self, index: u64) -> Value {
+        match *self {
+            Self::Integers { start, step, .. } => {
+                Value::Integer((start as i128 + index as i128 * step as i128) as i64)
+            }
+            Self::Floats { start, step, .. } => Value::Float(start + index as f64 * step),
+        }
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Integers { start, end, step } => write!(f, "range({}, {}, {})", start, end, step),
+            Self::Floats { start, end, step } => write!(
+                f,
+                "range({}, {}, {})",
+                format_float(start),
+                format_float(end),
+                format_float(step)
+            ),
+        }
+    }
+}
@@ -1,29 +1,107 @@
-use std::fmt::Display;
+use std::{cell::RefCell, cmp::Ordering, fmt::Display, rc::Rc, sync::Arc};
 
-#[derive(PartialEq, Clone)]
+use crate::lexer::format_float;
+
+use super::{
+    function::Function, heap, map::OrderedMap, module::Module, natives::NativeFunction,
+    range::Range,
+};
+
+// Printing recurses once per level of nesting, so deeper levels are elided to protect the stack
+const MAX_DISPLAY_DEPTH: usize = 64;
+
+// A list stored in several places prints once for each of them, so a small value can print as a
+// huge tree. Printing gives up with "..." after this many values.
+const MAX_DISPLAY_VALUES: usize = 1 << 20;
+
+#[derive(Clone)]
 pub enum Value {
     Boolean(bool),
-    Number(f64),
-    String(String),
+    Integer(i64),
+    Float(f64),
+    String(Arc<str>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<OrderedMap>>),
+    Range(Range),
+    NativeFunction(Rc<NativeFunction>),
+    Function(Rc<Function>),
+    Module(Rc<Module>),
     Nil,
 }
 
-#[derive(PartialEq, PartialOrd)]
-pub struct NumberPair(pub f64, pub f64);
+impl Value {
+    pub fn list(values: Vec<Value>) -> Self {
+        let list = Rc::new(RefCell::new(values));
+        heap::register_list(&list);
+        Self::List(list)
+    }
+
+    pub fn map(entries: OrderedMap) -> Self {
+        let map = Rc::new(RefCell::new(entries));
+        heap::register_map(&map);
+        Self::Map(map)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Boolean(left), Self::Boolean(right)) => left == right,
+            (Self::Integer(left), Self::Integer(right)) => left == right,
+            (Self::Float(left), Self::Float(right)) => left == right,
+            (Self::Integer(left), Self::Float(right)) => *left as f64 == *right,
+            (Self::Float(left), Self::Integer(right)) => *left == *right as f64,
+            (Self::String(left), Self::String(right)) => Arc::ptr_eq(left, right) || left == right,
+            (Self::List(left), Self::List(right)) => Rc::ptr_eq(left, right),
+            (Self::Map(left), Self::Map(right)) => Rc::ptr_eq(left, right),
+            (Self::Range(left), Self::Range(right)) => left == right,
+            (Self::NativeFunction(left), Self::NativeFunction(right)) => Rc::ptr_eq(left, right),
+            (Self::Function(left), Self::Function(right)) => Rc::ptr_eq(left, right),
+            (Self::Module(left), Self::Module(right)) => Rc::ptr_eq(left, right),
+            (Self::Nil, Self::Nil) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum NumberPair {
+    Integers(i64, i64),
+    Floats(f64, f64),
+}
+
+impl NumberPair {
+    pub fn as_floats(&self) -> (f64, f64) {
+        match self {
+            Self::Integers(left, right) => (*left as f64, *right as f64),
+            Self::Floats(left, right) => (*left, *right),
+        }
+    }
+
+    pub fn compare(&self) -> Option<Ordering> {
+        match self {
+            Self::Integers(left, right) => Some(left.cmp(right)),
+            Self::Floats(left, right) => left.partial_cmp(right),
+        }
+    }
+}
 
 impl TryFrom<(&Value, &Value)> for NumberPair {
     type Error = ();
 
     fn try_from(value: (&Value, &Value)) -> Result<Self, Self::Error> {
         match value {
-            (Value::Number(left), Value::Number(right)) => Ok(NumberPair(*left, *right)),
+            (Value::Integer(left), Value::Integer(right)) => Ok(Self::Integers(*left, *right)),
+            (Value::Integer(left), Value::Float(right)) => Ok(Self::Floats(*left as f64, *right)),
+            (Value::Float(left), Value::Integer(right)) => Ok(Self::Floats(*left, *right as f64)),
+            (Value::Float(left), Value::Float(right)) => Ok(Self::Floats(*left, *right)),
             _ => Err(()),
         }
     }
 }
 
 #[derive(PartialEq)]
-pub struct StringPair(pub String, pub String);
+pub struct StringPair(pub Arc<str>, pub Arc<str>);
 
 impl TryFrom<(&Value, &Value)> for StringPair {
     type Error = ();
@@ -31,24 +109,101 @@ impl TryFrom<(&Value, &Value)> for StringPair {
     fn try_from(value: (&Value, &Value)) -> Result<Self, Self::Error> {
         match value {
             (Value::String(left), Value::String(right)) => {
-                Ok(StringPair(left.to_string(), right.to_string()))
+                Ok(StringPair(left.clone(), right.clone()))
             }
             _ => Err(()),
         }
     }
 }
 
+impl std::fmt::Debug for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Nil => "nil".to_string(),
-                Self::Boolean(value) => value.to_string(),
-                Self::String(value) => value.to_string(),
-                Self::Number(value) => value.to_string(),
+        self.write(f, &mut DisplayState::default())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct DisplayState {
+    // The lists and maps being printed, so one nested in itself prints as [...] or {...} instead
+    // of recursing forever. Very deep nesting is cut off the same way.
+    open: Vec<*const ()>,
+    values: usize,
+}
+
+impl DisplayState {
+    fn is_full(&self) -> bool {
+        self.values > MAX_DISPLAY_VALUES
+    }
+}
+
+impl Value {
+    pub(crate) fn write(
+        &self,
+        f: &mut impl std::fmt::Write,
+        state: &mut DisplayState,
+    ) -> std::fmt::Result {
+        state.values += 1;
+        if state.is_full() {
+            return write!(f, "...");
+        }
+
+        match self {
+            Self::Nil => write!(f, "nil"),
+            Self::Boolean(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", format_float(*value)),
+            Self::List(values) => {
+                let pointer = Rc::as_ptr(values) as *const ();
+                if state.open.len() >= MAX_DISPLAY_DEPTH || state.open.contains(&pointer) {
+                    return write!(f, "[...]");
+                }
+
+                state.open.push(pointer);
+                write!(f, "[")?;
+                for (index, value) in values.borrow().iter().enumerate() {
+                    if state.is_full() {
+                        break;
+                    }
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    value.write(f, state)?;
+                }
+                state.open.pop();
+                write!(f, "]")
             }
-        )
+            Self::Map(entries) => {
+                let pointer = Rc::as_ptr(entries) as *const ();
+                if state.open.len() >= MAX_DISPLAY_DEPTH || state.open.contains(&pointer) {
+                    return write!(f, "{{...}}");
+                }
+
+                state.open.push(pointer);
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.borrow().iter().enumerate() {
+                    if state.is_full() {
+                        break;
+                    }
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: ", Value::from(key))?;
+                    value.write(f, state)?;
+                }
+                state.open.pop();
+                write!(f, "}}")
+            }
+            Self::Range(range) => write!(f, "{}", range),
+            Self::NativeFunction(native) => write!(f, "<native fn {}>", native.name),
+            Self::Function(function) => write!(f, "<fn {}>", function.name()),
+            Self::Module(module) => write!(f, "<module {}>", module.name),
+        }
     }
 }
@@ -3,19 +3,29 @@ use std::{collections::HashMap, fmt::Display, sync::LazyLock};
 pub(super) static KEYWORDS: LazyLock<HashMap<&'static str, Keyword>> = LazyLock::new(|| {
     HashMap::from([
         ("and", Keyword::And),
+        ("break", Keyword::Break),
+        ("catch", Keyword::Catch),
         ("class", Keyword::Class),
+        ("const", Keyword::Const),
+        ("continue", Keyword::Continue),
         ("else", Keyword::Else),
+        ("export", Keyword::Export),
         ("false", Keyword::False),
+        ("finally", Keyword::Finally),
         ("for", Keyword::For),
         ("fun", Keyword::Fun),
         ("if", Keyword::If),
+        ("import", Keyword::Import),
+        ("in", Keyword::In),
         ("nil", Keyword::Nil),
         ("or", Keyword::Or),
         ("print", Keyword::Print),
         ("return", Keyword::Return),
         ("super", Keyword::Super),
         ("this", Keyword::This),
+        ("throw", Keyword::Throw),
         ("true", Keyword::True),
+        ("try", Keyword::Try),
         ("var", Keyword::Var),
         ("while", Keyword::While),
     ])
@@ -24,19 +34,29 @@ pub(super) static KEYWORDS: LazyLock<HashMap<&'static str, Keyword>> = LazyLock:
 #[derive(Debug, Clone, PartialEq)]
 pub enum Keyword {
     And,
+    Break,
+    Catch,
     Class,
+    Const,
+    Continue,
     Else,
+    Export,
     False,
+    Finally,
     For,
     Fun,
     If,
+    Import,
+    In,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
 }
@@ -48,19 +68,29 @@ impl Display for Keyword {
             "{}",
             match self {
                 Self::And => "and",
+                Self::Break => "break",
+                Self::Catch => "catch",
                 Self::Class => "class",
+                Self::Const => "const",
+                Self::Continue => "continue",
                 Self::Else => "else",
+                Self::Export => "export",
                 Self::False => "false",
+                Self::Finally => "finally",
                 Self::For => "for",
                 Self::Fun => "fun",
                 Self::If => "if",
+                Self::Import => "import",
+                Self::In => "in",
                 Self::Nil => "nil",
                 Self::Or => "or",
                 Self::Print => "print",
                 Self::Return => "return",
                 Self::Super => "super",
                 Self::This => "this",
+                Self::Throw => "throw",
                 Self::True => "true",
+                Self::Try => "try",
                 Self::Var => "var",
                 Self::While => "while",
             }
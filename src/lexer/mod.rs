@@ -4,21 +4,24 @@ pub mod token;
 
 use std::{iter::Peekable, str::Chars};
 
+use crate::interner::Interner;
+
 pub use error::*;
 pub use keywords::*;
 pub use token::*;
 
-#[derive(Debug)]
 pub struct Lexer<'a> {
     source: Peekable<Chars<'a>>,
+    interner: &'a mut Interner,
     line_number: usize,
     eof: bool,
 }
 
 impl<'a> Lexer<'a> {
-    pub fn new(source: &'a str) -> Self {
+    pub fn new(source: &'a str, interner: &'a mut Interner) -> Self {
         Self {
             source: source.chars().peekable(),
+            interner,
             line_number: 1,
             eof: false,
         }
@@ -32,7 +35,8 @@ impl<'a> Lexer<'a> {
         let mut content = String::new();
         for c in self.source.by_ref() {
             if c == '"' {
-                return Ok(Token::new(TokenType::String(content), self.line_number));
+                let value = self.interner.intern(&content);
+                return Ok(Token::new(TokenType::String(value), self.line_number));
             } else if c == '\n' {
                 self.line_number += 1;
                 content.push(c);
@@ -88,7 +92,10 @@ impl<'a> Lexer<'a> {
         if let Some((_, token)) = KEYWORDS.get_key_value(&*value) {
             Token::new(TokenType::Keyword(token.to_owned()), self.line_number)
         } else {
-            Token::new(TokenType::Ident(value), self.line_number)
+            Token::new(
+                TokenType::Ident(self.interner.symbol(&value)),
+                self.line_number,
+            )
         }
     }
 }
@@ -103,14 +110,48 @@ impl<'a> Iterator for Lexer<'a> {
             let token = match self.source.next() {
                 Some('{') => Ok(Token::new(TokenType::LeftBrace, self.line_number)),
                 Some('}') => Ok(Token::new(TokenType::RightBrace, self.line_number)),
+                Some('[') => Ok(Token::new(TokenType::LeftBracket, self.line_number)),
+                Some(']') => Ok(Token::new(TokenType::RightBracket, self.line_number)),
                 Some('(') => Ok(Token::new(TokenType::LeftParen, self.line_number)),
                 Some(')') => Ok(Token::new(TokenType::RightParen, self.line_number)),
                 Some(';') => Ok(Token::new(TokenType::Semicolon, self.line_number)),
+                Some(':') => Ok(Token::new(TokenType::Colon, self.line_number)),
+                Some('?') => Ok(Token::new(TokenType::Question, self.line_number)),
                 Some(',') => Ok(Token::new(TokenType::Comma, self.line_number)),
-                Some('.') => Ok(Token::new(TokenType::Dot, self.line_number)),
-                Some('*') => Ok(Token::new(TokenType::Star, self.line_number)),
-                Some('+') => Ok(Token::new(TokenType::Plus, self.line_number)),
-                Some('-') => Ok(Token::new(TokenType::Minus, self.line_number)),
+                Some('.') => {
+                    if self.source.clone().take(2).eq(['.', '.']) {
+                        self.source.nth(1);
+                        Ok(Token::new(TokenType::Ellipsis, self.line_number))
+                    } else {
+                        Ok(Token::new(TokenType::Dot, self.line_number))
+                    }
+                }
+                Some('%') => Ok(Token::new(TokenType::Percent, self.line_number)),
+                Some('&') => Ok(Token::new(TokenType::Ampersand, self.line_number)),
+                Some('|') => Ok(Token::new(TokenType::Pipe, self.line_number)),
+                Some('^') => Ok(Token::new(TokenType::Caret, self.line_number)),
+                Some('+') => {
+                    if self.source.peek() == Some(&'+') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::PlusPlus, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::PlusEqual, self.line_number))
+                    } else {
+                        Ok(Token::new(TokenType::Plus, self.line_number))
+                    }
+                }
+                Some('-') => {
+                    if self.source.peek() == Some(&'-') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::MinusMinus, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::MinusEqual, self.line_number))
+                    } else {
+                        Ok(Token::new(TokenType::Minus, self.line_number))
+                    }
+                }
                 Some(' ') => Ok(Token::new(TokenType::Whitespace, self.line_number)),
                 Some('\t') => Ok(Token::new(TokenType::Tab, self.line_number)),
                 Some('\n') => {
@@ -126,6 +167,9 @@ impl<'a> Iterator for Lexer<'a> {
                             self.source.next();
                         }
                         Ok(Token::new(TokenType::Comment, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::SlashEqual, self.line_number))
                     } else {
                         Ok(Token::new(TokenType::Slash, self.line_number))
                     }
@@ -134,6 +178,9 @@ impl<'a> Iterator for Lexer<'a> {
                     if self.source.peek() == Some(&'=') {
                         self.source.next();
                         Ok(Token::new(TokenType::EqualEqual, self.line_number))
+                    } else if self.source.peek() == Some(&'>') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::Arrow, self.line_number))
                     } else {
                         Ok(Token::new(TokenType::Equal, self.line_number))
                     }
@@ -146,8 +193,22 @@ impl<'a> Iterator for Lexer<'a> {
                         Ok(Token::new(TokenType::Bang, self.line_number))
                     }
                 }
+                Some('*') => {
+                    if self.source.peek() == Some(&'*') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::StarStar, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::StarEqual, self.line_number))
+                    } else {
+                        Ok(Token::new(TokenType::Star, self.line_number))
+                    }
+                }
                 Some('<') => {
-                    if self.source.peek() == Some(&'=') {
+                    if self.source.peek() == Some(&'<') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::LessLess, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
                         self.source.next();
                         Ok(Token::new(TokenType::LessEqual, self.line_number))
                     } else {
@@ -155,13 +216,24 @@ impl<'a> Iterator for Lexer<'a> {
                     }
                 }
                 Some('>') => {
-                    if self.source.peek() == Some(&'=') {
+                    if self.source.peek() == Some(&'>') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::GreaterGreater, self.line_number))
+                    } else if self.source.peek() == Some(&'=') {
                         self.source.next();
                         Ok(Token::new(TokenType::GreaterEqual, self.line_number))
                     } else {
                         Ok(Token::new(TokenType::Greater, self.line_number))
                     }
                 }
+                Some('~') => {
+                    if self.source.peek() == Some(&'/') {
+                        self.source.next();
+                        Ok(Token::new(TokenType::TildeSlash, self.line_number))
+                    } else {
+                        Ok(Token::new(TokenType::Tilde, self.line_number))
+                    }
+                }
                 Some('"') => self.handle_string_literal(),
                 Some(c) => {
                     if c.is_numeric() {
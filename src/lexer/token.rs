@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{fmt::Display, sync::Arc};
+
+use crate::interner::Symbol;
 
 use super::Keyword;
 
@@ -36,27 +38,48 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Question,
     Comma,
     Dot,
+    Ellipsis,
     Minus,
     Plus,
     Semicolon,
     Slash,
     Star,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
 
     // One or two character tokens
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    Arrow,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
+    TildeSlash,
+    StarStar,
+    LessLess,
+    GreaterGreater,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PlusPlus,
+    MinusMinus,
 
     // Literals
-    Ident(String),
-    String(String),
+    Ident(Symbol),
+    String(Arc<str>),
     Number { lexeme: String, literal: f64 },
 
     // Keyword
@@ -78,21 +101,42 @@ impl TokenType {
             Self::RightParen => ")".to_string(),
             Self::LeftBrace => "{".to_string(),
             Self::RightBrace => "}".to_string(),
+            Self::LeftBracket => "[".to_string(),
+            Self::RightBracket => "]".to_string(),
+            Self::Colon => ":".to_string(),
+            Self::Question => "?".to_string(),
             Self::Comma => ",".to_string(),
             Self::Dot => ".".to_string(),
+            Self::Ellipsis => "...".to_string(),
             Self::Minus => "-".to_string(),
             Self::Plus => "+".to_string(),
             Self::Semicolon => ";".to_string(),
             Self::Slash => "/".to_string(),
             Self::Star => "*".to_string(),
+            Self::Percent => "%".to_string(),
+            Self::Ampersand => "&".to_string(),
+            Self::Pipe => "|".to_string(),
+            Self::Caret => "^".to_string(),
+            Self::Tilde => "~".to_string(),
             Self::Bang => "!".to_string(),
             Self::BangEqual => "!=".to_string(),
             Self::Equal => "=".to_string(),
             Self::EqualEqual => "==".to_string(),
+            Self::Arrow => "=>".to_string(),
             Self::Greater => ">".to_string(),
             Self::GreaterEqual => ">=".to_string(),
             Self::Less => "<".to_string(),
             Self::LessEqual => "<=".to_string(),
+            Self::TildeSlash => "~/".to_string(),
+            Self::StarStar => "**".to_string(),
+            Self::LessLess => "<<".to_string(),
+            Self::GreaterGreater => ">>".to_string(),
+            Self::PlusEqual => "+=".to_string(),
+            Self::MinusEqual => "-=".to_string(),
+            Self::StarEqual => "*=".to_string(),
+            Self::SlashEqual => "/=".to_string(),
+            Self::PlusPlus => "++".to_string(),
+            Self::MinusMinus => "--".to_string(),
             Self::Ident(ident) => ident.to_string(),
             Self::String(string) => format!("\"{}\"", string),
             Self::Number { lexeme, literal: _ } => lexeme.to_string(),
@@ -105,20 +149,22 @@ impl TokenType {
     pub fn literal(&self) -> String {
         match self {
             Self::String(string) => string.to_string(),
-            Self::Number { lexeme: _, literal } => {
-                let mut stringified = literal.to_string();
-                if stringified.contains('.') {
-                    stringified
-                } else {
-                    stringified.push_str(".0");
-                    stringified
-                }
-            }
+            Self::Number { lexeme: _, literal } => format_float(*literal),
             _ => "null".to_string(),
         }
     }
 }
 
+pub fn format_float(value: f64) -> String {
+    let mut stringified = value.to_string();
+    if stringified.contains('.') || !value.is_finite() {
+        stringified
+    } else {
+        stringified.push_str(".0");
+        stringified
+    }
+}
+
 impl PartialEq for TokenType {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -127,21 +173,42 @@ impl PartialEq for TokenType {
             | (TokenType::RightParen, TokenType::RightParen)
             | (TokenType::LeftBrace, TokenType::LeftBrace)
             | (TokenType::RightBrace, TokenType::RightBrace)
+            | (TokenType::LeftBracket, TokenType::LeftBracket)
+            | (TokenType::RightBracket, TokenType::RightBracket)
+            | (TokenType::Colon, TokenType::Colon)
+            | (TokenType::Question, TokenType::Question)
             | (TokenType::Comma, TokenType::Comma)
             | (TokenType::Dot, TokenType::Dot)
+            | (TokenType::Ellipsis, TokenType::Ellipsis)
             | (TokenType::Minus, TokenType::Minus)
             | (TokenType::Plus, TokenType::Plus)
             | (TokenType::Semicolon, TokenType::Semicolon)
             | (TokenType::Slash, TokenType::Slash)
             | (TokenType::Star, TokenType::Star)
+            | (TokenType::Percent, TokenType::Percent)
+            | (TokenType::Ampersand, TokenType::Ampersand)
+            | (TokenType::Pipe, TokenType::Pipe)
+            | (TokenType::Caret, TokenType::Caret)
+            | (TokenType::Tilde, TokenType::Tilde)
             | (TokenType::Bang, TokenType::Bang)
             | (TokenType::BangEqual, TokenType::BangEqual)
             | (TokenType::Equal, TokenType::Equal)
             | (TokenType::EqualEqual, TokenType::EqualEqual)
+            | (TokenType::Arrow, TokenType::Arrow)
             | (TokenType::Greater, TokenType::Greater)
             | (TokenType::GreaterEqual, TokenType::GreaterEqual)
             | (TokenType::Less, TokenType::Less)
             | (TokenType::LessEqual, TokenType::LessEqual)
+            | (TokenType::TildeSlash, TokenType::TildeSlash)
+            | (TokenType::StarStar, TokenType::StarStar)
+            | (TokenType::LessLess, TokenType::LessLess)
+            | (TokenType::GreaterGreater, TokenType::GreaterGreater)
+            | (TokenType::PlusEqual, TokenType::PlusEqual)
+            | (TokenType::MinusEqual, TokenType::MinusEqual)
+            | (TokenType::StarEqual, TokenType::StarEqual)
+            | (TokenType::SlashEqual, TokenType::SlashEqual)
+            | (TokenType::PlusPlus, TokenType::PlusPlus)
+            | (TokenType::MinusMinus, TokenType::MinusMinus)
             | (TokenType::Whitespace, TokenType::Whitespace)
             | (TokenType::Tab, TokenType::Tab)
             | (TokenType::NewLine, TokenType::NewLine)
@@ -169,21 +236,42 @@ impl Display for TokenType {
                 Self::RightParen => "RIGHT_PAREN",
                 Self::LeftBrace => "LEFT_BRACE",
                 Self::RightBrace => "RIGHT_BRACE",
+                Self::LeftBracket => "LEFT_BRACKET",
+                Self::RightBracket => "RIGHT_BRACKET",
+                Self::Colon => "COLON",
+                Self::Question => "QUESTION",
                 Self::Comma => "COMMA",
                 Self::Dot => "DOT",
+                Self::Ellipsis => "ELLIPSIS",
                 Self::Minus => "MINUS",
                 Self::Plus => "PLUS",
                 Self::Semicolon => "SEMICOLON",
                 Self::Slash => "SLASH",
                 Self::Star => "STAR",
+                Self::Percent => "PERCENT",
+                Self::Ampersand => "AMPERSAND",
+                Self::Pipe => "PIPE",
+                Self::Caret => "CARET",
+                Self::Tilde => "TILDE",
                 Self::Bang => "BANG",
                 Self::BangEqual => "BANG_EQUAL",
                 Self::Equal => "EQUAL",
                 Self::EqualEqual => "EQUAL_EQUAL",
+                Self::Arrow => "ARROW",
                 Self::Greater => "GREATER",
                 Self::GreaterEqual => "GREATER_EQUAL",
                 Self::Less => "LESS",
                 Self::LessEqual => "LESS_EQUAL",
+                Self::TildeSlash => "TILDE_SLASH",
+                Self::StarStar => "STAR_STAR",
+                Self::LessLess => "LESS_LESS",
+                Self::GreaterGreater => "GREATER_GREATER",
+                Self::PlusEqual => "PLUS_EQUAL",
+                Self::MinusEqual => "MINUS_EQUAL",
+                Self::StarEqual => "STAR_EQUAL",
+                Self::SlashEqual => "SLASH_EQUAL",
+                Self::PlusPlus => "PLUS_PLUS",
+                Self::MinusMinus => "MINUS_MINUS",
                 Self::Ident(_) => "IDENTIFIER",
                 Self::String(_) => "STRING",
                 Self::Number {
@@ -1,4 +1,5 @@
 pub mod error;
+pub mod interner;
 pub mod interpreter;
 pub mod lexer;
 pub mod parser;
@@ -1,3 +1,52 @@
-fn main() {
-    println!("Hello, world!");
+use std::{env, fs, process::ExitCode};
+
+use lox_interpreter::{error::LoxError, interpreter::Interpreter};
+
+fn main() -> ExitCode {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Usage: lox_interpreter <script> [args...]");
+        return ExitCode::from(64);
+    };
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", path, err);
+            return ExitCode::from(66);
+        }
+    };
+
+    // Scripts get the deep call limit of a dedicated large-stack thread
+    let interpreter_thread = Interpreter::spawn_with_large_stack(move |interpreter| {
+        match run(interpreter, &path, &source, env::args().skip(2).collect()) {
+            Ok(()) => 0,
+            Err(err) => {
+                eprint!("{}", err);
+                match err {
+                    LoxError::LexerError(_) | LoxError::ParseError(_) => 65,
+                    LoxError::RuntimeError(_) => 70,
+                }
+            }
+        }
+    });
+
+    match interpreter_thread.map(|handle| handle.join()) {
+        Ok(Ok(code)) => ExitCode::from(code),
+        _ => ExitCode::from(70),
+    }
+}
+
+fn run(
+    interpreter: &mut Interpreter,
+    path: &str,
+    source: &str,
+    args: Vec<String>,
+) -> Result<(), LoxError> {
+    // Library users opt in to the filesystem, but scripts run from the command line get it
+    interpreter.set_filesystem_access(true);
+    interpreter.set_script_path(path);
+    interpreter.set_script_args(args);
+    let program = interpreter.parse(source)?;
+    interpreter.interpret(&program)?;
+    Ok(())
 }
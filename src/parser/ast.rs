@@ -0,0 +1,93 @@
+use std::{ops::Index, rc::Rc};
+
+use super::{stmt::Stmt, Expr, ExprId, NodeId, StmtId};
+
+// The arena that owns every node of one parse. Nodes refer to their children by id, and the
+// slots are indexed by id relative to the first one handed to this parse.
+#[derive(Debug)]
+pub struct Ast {
+    first: usize,
+    next: usize,
+    nodes: Vec<Option<Node>>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Expr(Expr),
+    Stmt(Stmt),
+}
+
+impl Ast {
+    pub(crate) fn new(first: NodeId) -> Self {
+        Self {
+            first: first.index(),
+            next: first.index(),
+            nodes: vec![],
+        }
+    }
+
+    pub(crate) fn next_id(&mut self) -> NodeId {
+        let id = NodeId::new(self.next);
+        self.next += 1;
+        id
+    }
+
+    // One past the highest id handed out, where the next parse should start
+    pub(crate) fn end(&self) -> NodeId {
+        NodeId::new(self.next)
+    }
+
+    pub(crate) fn add_expr(&mut self, expr: Expr) -> ExprId {
+        let id = expr.id();
+        self.insert(id, Node::Expr(expr));
+        ExprId(id)
+    }
+
+    pub(crate) fn add_stmt(&mut self, stmt: Stmt) -> StmtId {
+        let id = stmt.id();
+        self.insert(id, Node::Stmt(stmt));
+        StmtId(id)
+    }
+
+    fn insert(&mut self, id: NodeId, node: Node) {
+        let slot = id.index() - self.first;
+        if slot >= self.nodes.len() {
+            self.nodes.resize_with(slot + 1, || None);
+        }
+        self.nodes[slot] = Some(node);
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node> {
+        let slot = id.index().checked_sub(self.first)?;
+        self.nodes.get(slot).and_then(Option::as_ref)
+    }
+}
+
+impl Index<ExprId> for Ast {
+    type Output = Expr;
+
+    fn index(&self, id: ExprId) -> &Expr {
+        match self.node(id.0) {
+            Some(Node::Expr(expr)) => expr,
+            _ => panic!("{:?} is not an expression of this tree", id),
+        }
+    }
+}
+
+impl Index<StmtId> for Ast {
+    type Output = Stmt;
+
+    fn index(&self, id: StmtId) -> &Stmt {
+        match self.node(id.0) {
+            Some(Node::Stmt(stmt)) => stmt,
+            _ => panic!("{:?} is not a statement of this tree", id),
+        }
+    }
+}
+
+// A parsed script: its top-level statements and the tree they live in
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub ast: Rc<Ast>,
+    pub statements: Vec<StmtId>,
+}
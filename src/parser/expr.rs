@@ -1,22 +1,90 @@
-use std::fmt::Display;
+use std::{fmt::Display, rc::Rc, sync::Arc};
 
-use crate::{define_ast, lexer::Token};
+use crate::{
+    define_ast,
+    interner::Symbol,
+    lexer::{format_float, Token},
+};
+
+use super::{ExprId, StmtId};
 
 define_ast!(Expr,
-    Assign(assign) { name: Token, value: Box<Expr> },
-    Binary(binary) { left: Box<Expr>, operator: BinaryOp, right: Box<Expr>, line_number: usize },
-    Grouping(grouping) { expression: Box<Expr>, line_number: usize  },
+    Assign(assign) { name: Token, value: ExprId },
+    Binary(binary) { left: ExprId, operator: BinaryOp, right: ExprId, line_number: usize },
+    CompoundAssign(compound_assign) { target: ExprId, operator: BinaryOp, value: ExprId, line_number: usize },
+    Increment(increment) { target: ExprId, operator: BinaryOp, prefix: bool, line_number: usize },
+    Conditional(conditional) { condition: ExprId, then_branch: ExprId, else_branch: ExprId, line_number: usize },
+    Call(call) { callee: ExprId, arguments: Vec<ExprId>, named_arguments: Vec<(Symbol, ExprId)>, line_number: usize },
+    Grouping(grouping) { expression: ExprId, line_number: usize  },
+    Lambda(lambda) { function: Rc<FunctionDecl>, line_number: usize },
+    Get(get) { object: ExprId, name: Symbol, line_number: usize },
+    Index(index) { object: ExprId, index: ExprId, line_number: usize },
+    List(list) { elements: Vec<ExprId>, line_number: usize },
+    Map(map) { entries: Vec<(ExprId, ExprId)>, line_number: usize },
     Literal(literal) { literal_type: LiteralType, line_number: usize  },
-    Unary(unary) { operator: UnaryOp, right: Box<Expr>, line_number: usize  },
+    SetIndex(set_index) { object: ExprId, index: ExprId, value: ExprId, line_number: usize },
+    Unary(unary) { operator: UnaryOp, right: ExprId, line_number: usize  },
     Variable(variable) { name: Token },
 );
 
+impl Expr {
+    pub fn line_number(&self) -> usize {
+        match self {
+            Self::Assign(expr) => expr.name.line_number,
+            Self::Binary(expr) => expr.line_number,
+            Self::CompoundAssign(expr) => expr.line_number,
+            Self::Increment(expr) => expr.line_number,
+            Self::Conditional(expr) => expr.line_number,
+            Self::Call(expr) => expr.line_number,
+            Self::Grouping(expr) => expr.line_number,
+            Self::Lambda(expr) => expr.line_number,
+            Self::Get(expr) => expr.line_number,
+            Self::Index(expr) => expr.line_number,
+            Self::List(expr) => expr.line_number,
+            Self::Map(expr) => expr.line_number,
+            Self::Literal(expr) => expr.line_number,
+            Self::SetIndex(expr) => expr.line_number,
+            Self::Unary(expr) => expr.line_number,
+            Self::Variable(expr) => expr.name.line_number,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FunctionDecl {
+    // The variable, constant or parameter the function was written as the value of, if any
+    pub name: Option<Symbol>,
+    pub params: Vec<Parameter>,
+    pub rest: Option<Symbol>,
+    pub body: FunctionBody,
+}
+
+#[derive(Debug)]
+pub struct Parameter {
+    pub name: Symbol,
+    pub default: Option<ExprId>,
+}
+
+#[derive(Debug)]
+pub enum FunctionBody {
+    Block(Vec<StmtId>),
+    Expression(ExprId),
+}
+
 #[derive(Debug, Clone)]
 pub enum BinaryOp {
     Plus,
     Minus,
     Mul,
     Div,
+    IntDiv,
+    Mod,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     Greater,
     GreaterEqual,
     Less,
@@ -35,6 +103,14 @@ impl Display for BinaryOp {
                 Self::Minus => "-",
                 Self::Mul => "*",
                 Self::Div => "/",
+                Self::IntDiv => "~/",
+                Self::Mod => "%",
+                Self::Pow => "**",
+                Self::BitAnd => "&",
+                Self::BitOr => "|",
+                Self::BitXor => "^",
+                Self::ShiftLeft => "<<",
+                Self::ShiftRight => ">>",
                 Self::Greater => ">",
                 Self::GreaterEqual => ">=",
                 Self::Less => "<",
@@ -48,8 +124,9 @@ impl Display for BinaryOp {
 
 #[derive(Debug, Clone)]
 pub enum LiteralType {
-    String { value: String },
-    Number { value: f64 },
+    String { value: Arc<str> },
+    Integer { value: i64 },
+    Float { value: f64 },
     Bool { value: bool },
     Nil,
 }
@@ -63,15 +140,8 @@ impl Display for LiteralType {
                 Self::Nil => "nil".to_string(),
                 Self::Bool { value } => value.to_string(),
                 Self::String { value } => value.to_string(),
-                Self::Number { value } => {
-                    let mut stringified = value.to_string();
-                    if stringified.contains('.') {
-                        stringified
-                    } else {
-                        stringified.push_str(".0");
-                        stringified
-                    }
-                }
+                Self::Integer { value } => value.to_string(),
+                Self::Float { value } => format_float(*value),
             }
         )
     }
@@ -87,6 +157,7 @@ impl Display for Literal {
 pub enum UnaryOp {
     Bang,
     Minus,
+    BitNot,
 }
 
 impl Display for UnaryOp {
@@ -97,6 +168,7 @@ impl Display for UnaryOp {
             match self {
                 Self::Bang => '!',
                 Self::Minus => '-',
+                Self::BitNot => '~',
             }
         )
     }
@@ -3,7 +3,7 @@ macro_rules! define_ast {
     ($enum_name:ident,
         $($variant_name:ident ( $lowercase_name:ident )  { $( $field_name:ident : $field_type:ty ),* }),* $(,)?) => {
 
-            #[derive(Debug, Clone)]
+            #[derive(Debug)]
             pub enum $enum_name {
                 $(
                     $variant_name($variant_name),
@@ -11,6 +11,14 @@ macro_rules! define_ast {
             }
 
             impl $enum_name {
+                pub fn id(&self) -> $crate::parser::NodeId {
+                    match self {
+                        $(
+                            $enum_name::$variant_name(inner) => inner.id,
+                        )*
+                    }
+                }
+
                 pub fn accept<T>(&self, visitor: &mut impl Visitor<T>) -> T {
                     match self {
                         $(
@@ -21,14 +29,15 @@ macro_rules! define_ast {
             }
 
             $(
-                #[derive(Debug, Clone)]
+                #[derive(Debug)]
                 pub struct $variant_name {
+                    pub id: $crate::parser::NodeId,
                     $(pub $field_name: $field_type,)*
                 }
 
                 impl $variant_name {
-                    pub fn new($($field_name: $field_type),*) -> Self {
-                        Self { $($field_name),* }
+                    pub fn new(id: $crate::parser::NodeId, $($field_name: $field_type),*) -> Self {
+                        Self { id, $($field_name),* }
                     }
 
                     pub fn accept<T>(&self, visitor: &mut impl Visitor<T>) -> T {
@@ -1,25 +1,72 @@
+pub mod ast;
 pub mod error;
 pub mod expr;
 pub(super) mod macros;
+pub mod node;
 pub mod stmt;
 
+pub use ast::*;
 pub use error::*;
 pub use expr::*;
-use stmt::{Block, Expression, Print, Stmt, Var};
-
-use crate::lexer::{Keyword, Token, TokenType};
-
-pub struct Parser<'a> {
+pub use node::*;
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+use stmt::{
+    Block, Break, Const, Continue, Export, Expression, ForIn, Import, Print, Return, Stmt, Throw,
+    Try, Var,
+};
+
+use crate::{
+    interner::Symbol,
+    lexer::{Keyword, Token, TokenType},
+};
+
+// Nodes are added to an Ast that the caller sets up, which is how the Interpreter keeps ids
+// unique across every script and module it parses
+pub(crate) struct Parser<'a> {
     tokens: &'a [Token],
+    ast: &'a mut Ast,
     current: usize,
+    // The index of the ')' that closes each '(', so telling an arrow lambda from a grouping
+    // doesn't rescan the tokens every time
+    closing_parens: Vec<Option<usize>>,
+    // The names declared in each enclosing scope, and whether each is a constant
+    scopes: Vec<HashMap<Symbol, bool>>,
+    loop_depth: usize,
+    function_depth: usize,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(tokens: &'a [Token]) -> Self {
-        Self { tokens, current: 0 }
+    pub(crate) fn new(tokens: &'a [Token], ast: &'a mut Ast) -> Self {
+        Self {
+            tokens,
+            ast,
+            current: 0,
+            closing_parens: Self::closing_parens(tokens),
+            scopes: vec![HashMap::new()],
+            loop_depth: 0,
+            function_depth: 0,
+        }
+    }
+
+    fn closing_parens(tokens: &[Token]) -> Vec<Option<usize>> {
+        let mut closing = vec![None; tokens.len()];
+        let mut open = vec![];
+        for (index, token) in tokens.iter().enumerate() {
+            match token.token_type {
+                TokenType::LeftParen => open.push(index),
+                TokenType::RightParen => {
+                    if let Some(left) = open.pop() {
+                        closing[left] = Some(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        closing
     }
 
-    pub fn parse(&mut self) -> ParseResult<Vec<Stmt>> {
+    pub(crate) fn parse(&mut self) -> ParseResult<Vec<StmtId>> {
         let mut stmts = vec![];
         let mut had_error = None;
 
@@ -28,10 +75,11 @@ impl<'a> Parser<'a> {
             if self.is_at_end() {
                 break;
             }
-            match self.declaration() {
-                Ok(stmt) => stmts.push(stmt),
+            match self.top_level_declaration() {
+                Ok(stmt) => stmts.push(self.add_stmt(stmt)),
                 Err(err) => {
-                    had_error = Some(err);
+                    // Later errors are often fallout from resynchronizing, so report the first
+                    had_error.get_or_insert(err);
                     self.synchronize();
                 }
             }
@@ -42,24 +90,117 @@ impl<'a> Parser<'a> {
             None => Ok(stmts),
         }
     }
-
-    pub fn parse_expr(&mut self) -> ParseResult<Expr> {
-        self.expression()
-    }
 }
 
 impl<'a> Parser<'a> {
+    fn top_level_declaration(&mut self) -> ParseResult<Stmt> {
+        if self.matched(&[TokenType::Keyword(Keyword::Import)]) {
+            return self.import_statement();
+        }
+        if self.check_contextual("from")
+            && matches!(self.peek_next().token_type, TokenType::String(_))
+        {
+            self.advance();
+            return self.selective_import_statement();
+        }
+        if self.matched(&[TokenType::Keyword(Keyword::Export)]) {
+            return self.export_declaration();
+        }
+        self.declaration()
+    }
+
+    fn import_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        let path = self.consume_string("Expect module path after 'import'.")?;
+        if !self.check_contextual("as") {
+            return Err(ParseError::new(
+                self.peek().clone(),
+                "Expect 'as' after module path.",
+            ));
+        }
+        self.advance();
+        let alias = self.consume_identifier()?;
+        self.consume(&TokenType::Semicolon, "Expect ';' after import.")?;
+        self.declare(&alias, false);
+
+        Ok(Stmt::Import(Import::new(
+            self.node_id(),
+            path,
+            Some(alias),
+            vec![],
+            keyword.line_number,
+        )))
+    }
+
+    fn selective_import_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        let path = self.consume_string("Expect module path after 'from'.")?;
+        self.consume(
+            &TokenType::Keyword(Keyword::Import),
+            "Expect 'import' after module path.",
+        )?;
+
+        let mut names = vec![];
+        loop {
+            names.push(self.consume_identifier()?);
+            if !self.matched(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.consume(&TokenType::Semicolon, "Expect ';' after import.")?;
+
+        Ok(Stmt::Import(Import::new(
+            self.node_id(),
+            path,
+            None,
+            names,
+            keyword.line_number,
+        )))
+    }
+
+    fn export_declaration(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        let declaration = self.declaration()?;
+        match declaration {
+            Stmt::Var(_) | Stmt::Const(_) => Ok(Stmt::Export(Export::new(
+                self.node_id(),
+                self.add_stmt(declaration),
+            ))),
+            _ => Err(ParseError::new(
+                keyword.clone(),
+                "Expect variable or constant declaration after 'export'.",
+            )),
+        }
+    }
+
     fn declaration(&mut self) -> ParseResult<Stmt> {
+        if self.matched(&[
+            TokenType::Keyword(Keyword::Import),
+            TokenType::Keyword(Keyword::Export),
+        ]) {
+            let keyword = self.previous();
+            return Err(ParseError::new(
+                keyword.clone(),
+                format!(
+                    "Can only use '{}' at the top level of a module.",
+                    keyword.token_type.lexeme()
+                )
+                .as_str(),
+            ));
+        }
         if self.matched(&[TokenType::Keyword(Keyword::Var)]) {
             let next = self.advance();
             return self.var_declaration(next);
         }
+        if self.matched(&[TokenType::Keyword(Keyword::Const)]) {
+            return self.const_declaration();
+        }
         self.statement()
     }
 
     fn var_declaration(&mut self, name_token: &Token) -> ParseResult<Stmt> {
-        let name = if let TokenType::Ident(ref name_str) = name_token.token_type {
-            name_str.clone()
+        let name = if let TokenType::Ident(name) = &name_token.token_type {
+            name.clone()
         } else {
             return Err(ParseError::new(
                 name_token.clone(),
@@ -70,15 +211,43 @@ impl<'a> Parser<'a> {
         let mut initializer = None;
 
         if self.matched(&[TokenType::Equal]) {
-            initializer = Some(self.expression()?);
+            initializer = Some(self.bound_expression(&name)?);
         }
 
         self.consume(
             &TokenType::Semicolon,
             "Expect ';' after variable declaration.",
         )?;
+        self.declare(&name, false);
+
+        Ok(Stmt::Var(Var::new(
+            self.node_id(),
+            name,
+            initializer,
+            name_token.line_number,
+        )))
+    }
 
-        Ok(Stmt::Var(Var::new(name, initializer)))
+    fn const_declaration(&mut self) -> ParseResult<Stmt> {
+        let name = self.consume_identifier()?;
+        let line_number = self.previous().line_number;
+        self.consume(
+            &TokenType::Equal,
+            "Expect '=' after constant name, constants must be initialized.",
+        )?;
+        let initializer = self.bound_expression(&name)?;
+        self.consume(
+            &TokenType::Semicolon,
+            "Expect ';' after constant declaration.",
+        )?;
+        self.declare(&name, true);
+
+        Ok(Stmt::Const(Const::new(
+            self.node_id(),
+            name,
+            initializer,
+            line_number,
+        )))
     }
 
     fn statement(&mut self) -> ParseResult<Stmt> {
@@ -88,32 +257,228 @@ impl<'a> Parser<'a> {
             return self.print_statement();
         }
 
+        if self.matched(&[TokenType::Keyword(Keyword::For)]) {
+            return self.for_statement();
+        }
+
+        if self.matched(&[
+            TokenType::Keyword(Keyword::Break),
+            TokenType::Keyword(Keyword::Continue),
+        ]) {
+            return self.loop_jump_statement();
+        }
+
+        if self.matched(&[TokenType::Keyword(Keyword::Return)]) {
+            return self.return_statement();
+        }
+
+        if self.matched(&[TokenType::Keyword(Keyword::Throw)]) {
+            return self.throw_statement();
+        }
+
+        if self.matched(&[TokenType::Keyword(Keyword::Try)]) {
+            return self.try_statement();
+        }
+
         if self.matched(&[TokenType::LeftBrace]) {
-            return Ok(Stmt::Block(Block::new(self.block()?)));
+            return Ok(Stmt::Block(Block::new(self.node_id(), self.block()?)));
         }
 
         self.expression_statement()
     }
 
-    fn block(&mut self) -> ParseResult<Vec<Stmt>> {
-        let mut statements = vec![];
+    fn for_statement(&mut self) -> ParseResult<Stmt> {
+        let for_token = self.previous();
+        self.consume(&TokenType::LeftParen, "Expect '(' after 'for'.")?;
+        self.consume(&TokenType::Keyword(Keyword::Var), "Expect 'var' after '('.")?;
+
+        let name = self.consume_identifier()?;
+        self.consume(
+            &TokenType::Keyword(Keyword::In),
+            "Expect 'in' after loop variable.",
+        )?;
+        let iterable = self.child_expression()?;
+        self.consume(&TokenType::RightParen, "Expect ')' after for clauses.")?;
+
+        self.loop_depth += 1;
+        let body = self.scoped(&[&name], Self::statement);
+        self.loop_depth -= 1;
+        let body = body?;
+
+        Ok(Stmt::ForIn(ForIn::new(
+            self.node_id(),
+            name,
+            iterable,
+            self.add_stmt(body),
+            for_token.line_number,
+        )))
+    }
 
-        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
-            statements.push(self.declaration()?);
+    fn loop_jump_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        if self.loop_depth == 0 {
+            return Err(ParseError::new(
+                keyword.clone(),
+                format!(
+                    "Can't use '{}' outside of a loop.",
+                    keyword.token_type.lexeme()
+                )
+                .as_str(),
+            ));
         }
 
-        self.consume(&TokenType::RightBrace, "Expect '}' after block.")?;
-        Ok(statements)
+        self.consume(
+            &TokenType::Semicolon,
+            format!("Expect ';' after '{}'.", keyword.token_type.lexeme()).as_str(),
+        )?;
+
+        Ok(match keyword.token_type {
+            TokenType::Keyword(Keyword::Break) => {
+                Stmt::Break(Break::new(self.node_id(), keyword.line_number))
+            }
+            _ => Stmt::Continue(Continue::new(self.node_id(), keyword.line_number)),
+        })
+    }
+
+    fn return_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        if self.function_depth == 0 {
+            return Err(ParseError::new(
+                keyword.clone(),
+                "Can't return from top-level code.",
+            ));
+        }
+
+        let mut value = None;
+        if !self.check(&TokenType::Semicolon) {
+            value = Some(self.child_expression()?);
+        }
+        self.consume(&TokenType::Semicolon, "Expect ';' after return value.")?;
+
+        Ok(Stmt::Return(Return::new(
+            self.node_id(),
+            value,
+            keyword.line_number,
+        )))
+    }
+
+    fn throw_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        let value = self.child_expression()?;
+        self.consume(&TokenType::Semicolon, "Expect ';' after thrown value.")?;
+        Ok(Stmt::Throw(Throw::new(
+            self.node_id(),
+            value,
+            keyword.line_number,
+        )))
+    }
+
+    fn try_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous();
+        self.consume(&TokenType::LeftBrace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+
+        let mut catch_name = None;
+        let mut catch_body = None;
+        if self.matched(&[TokenType::Keyword(Keyword::Catch)]) {
+            self.consume(&TokenType::LeftParen, "Expect '(' after 'catch'.")?;
+            let name = self.consume_identifier()?;
+            self.consume(&TokenType::RightParen, "Expect ')' after catch variable.")?;
+            self.consume(&TokenType::LeftBrace, "Expect '{' before catch body.")?;
+            catch_body = Some(self.scoped(&[&name], Self::block)?);
+            catch_name = Some(name);
+        }
+
+        let mut finally_body = None;
+        if self.matched(&[TokenType::Keyword(Keyword::Finally)]) {
+            self.consume(&TokenType::LeftBrace, "Expect '{' after 'finally'.")?;
+            finally_body = Some(self.block()?);
+        }
+
+        if catch_body.is_none() && finally_body.is_none() {
+            return Err(ParseError::new(
+                self.peek().clone(),
+                "Expect 'catch' or 'finally' after try block.",
+            ));
+        }
+
+        Ok(Stmt::Try(Try::new(
+            self.node_id(),
+            body,
+            catch_name,
+            catch_body,
+            finally_body,
+            keyword.line_number,
+        )))
+    }
+
+    fn block(&mut self) -> ParseResult<Vec<StmtId>> {
+        self.scoped(&[], |parser| {
+            let mut statements = vec![];
+
+            while !parser.check(&TokenType::RightBrace) && !parser.is_at_end() {
+                let statement = parser.declaration()?;
+                statements.push(parser.add_stmt(statement));
+            }
+
+            parser.consume(&TokenType::RightBrace, "Expect '}' after block.")?;
+            Ok(statements)
+        })
+    }
+
+    // Parses with a new scope holding `names`, which are never constants
+    fn scoped<T>(
+        &mut self,
+        names: &[&Symbol],
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.scopes
+            .push(names.iter().map(|name| ((*name).clone(), false)).collect());
+        let result = parse(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn declare(&mut self, name: &Symbol, constant: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.clone(), constant);
+        }
+    }
+
+    // Assigning to a constant the parser can see is rejected before anything runs. Constants it
+    // can't, such as imported ones or ones declared after the function that assigns them, are
+    // still caught by the interpreter.
+    fn check_not_constant(&self, target: &Expr) -> ParseResult<()> {
+        let Expr::Variable(variable) = target else {
+            return Ok(());
+        };
+        let TokenType::Ident(name) = &variable.name.token_type else {
+            return Ok(());
+        };
+
+        let constant = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .is_some_and(|constant| *constant);
+        if constant {
+            return Err(ParseError::new(
+                variable.name.clone(),
+                format!("Cannot assign to constant \"{}\".", name).as_str(),
+            ));
+        }
+        Ok(())
     }
 
     fn expression_statement(&mut self) -> ParseResult<Stmt> {
-        let expr = self.expression()?;
+        let expr = self.child_expression()?;
         self.consume(&TokenType::Semicolon, "Expect ';' afrer expression.")?;
-        Ok(Stmt::Expression(Expression::new(expr)))
+        Ok(Stmt::Expression(Expression::new(self.node_id(), expr)))
     }
 
     fn print_statement(&mut self) -> ParseResult<Stmt> {
-        let value = self.expression()?;
+        let value = self.child_expression()?;
         if self.is_at_end() {
             return Err(ParseError::new(
                 self.peek().clone(),
@@ -121,7 +486,7 @@ impl<'a> Parser<'a> {
             ));
         }
         self.consume(&TokenType::Semicolon, "Expect ';' after value.")?;
-        Ok(Stmt::Print(Print::new(value)))
+        Ok(Stmt::Print(Print::new(self.node_id(), value)))
     }
 
     fn expression(&mut self) -> ParseResult<Expr> {
@@ -129,27 +494,111 @@ impl<'a> Parser<'a> {
     }
 
     fn assignment(&mut self) -> ParseResult<Expr> {
-        let expr = self.equality()?;
+        let expr = self.conditional()?;
 
         if self.matched(&[TokenType::Equal]) {
             let equals = self.previous();
+            self.check_not_constant(&expr)?;
             let value = self.assignment()?;
 
             match expr {
                 Expr::Variable(var) => {
                     let name = var.name;
-                    Ok(Expr::Assign(Assign::new(name, Box::new(value))))
+                    Ok(Expr::Assign(Assign::new(
+                        self.node_id(),
+                        name,
+                        self.add_expr(value),
+                    )))
                 }
+                Expr::Index(index) => Ok(Expr::SetIndex(SetIndex::new(
+                    self.node_id(),
+                    index.object,
+                    index.index,
+                    self.add_expr(value),
+                    index.line_number,
+                ))),
                 _ => Err(ParseError::new(
                     equals.clone(),
                     "Invalid assignment target.",
                 )),
             }
+        } else if self.matched(&[
+            TokenType::PlusEqual,
+            TokenType::MinusEqual,
+            TokenType::StarEqual,
+            TokenType::SlashEqual,
+        ]) {
+            let token = self.previous();
+            let operator = match token.token_type {
+                TokenType::PlusEqual => BinaryOp::Plus,
+                TokenType::MinusEqual => BinaryOp::Minus,
+                TokenType::StarEqual => BinaryOp::Mul,
+                _ => BinaryOp::Div,
+            };
+            Self::check_assignment_target(&expr, token)?;
+            self.check_not_constant(&expr)?;
+            let value = self.assignment()?;
+
+            Ok(Expr::CompoundAssign(CompoundAssign::new(
+                self.node_id(),
+                self.add_expr(expr),
+                operator,
+                self.add_expr(value),
+                token.line_number,
+            )))
         } else {
             Ok(expr)
         }
     }
 
+    fn conditional(&mut self) -> ParseResult<Expr> {
+        let condition = self.equality()?;
+
+        if !self.matched(&[TokenType::Question]) {
+            return Ok(condition);
+        }
+
+        let line_number = self.previous().line_number;
+        let then_branch = self.expression()?;
+        self.consume(
+            &TokenType::Colon,
+            "Expect ':' after then branch of conditional expression.",
+        )?;
+        let else_branch = self.conditional()?;
+
+        Ok(Expr::Conditional(Conditional::new(
+            self.node_id(),
+            self.add_expr(condition),
+            self.add_expr(then_branch),
+            self.add_expr(else_branch),
+            line_number,
+        )))
+    }
+
+    fn check_assignment_target(target: &Expr, token: &Token) -> ParseResult<()> {
+        match target {
+            Expr::Variable(_) | Expr::Index(_) => Ok(()),
+            _ => Err(ParseError::new(token.clone(), "Invalid assignment target.")),
+        }
+    }
+
+    fn increment(&mut self, target: Expr, token: &Token, prefix: bool) -> ParseResult<Expr> {
+        Self::check_assignment_target(&target, token)?;
+        self.check_not_constant(&target)?;
+        let operator = match token.token_type {
+            TokenType::PlusPlus => BinaryOp::Plus,
+            _ => BinaryOp::Minus,
+        };
+
+        Ok(Expr::Increment(Increment::new(
+            self.node_id(),
+            self.add_expr(target),
+            operator,
+            prefix,
+            token.line_number,
+        )))
+    }
+
     fn equality(&mut self) -> ParseResult<Expr> {
         let mut expr = self.comparison()?;
 
@@ -165,9 +614,10 @@ impl<'a> Parser<'a> {
             };
             let right = self.comparison()?;
             expr = Expr::Binary(Binary::new(
-                Box::new(expr),
+                self.node_id(),
+                self.add_expr(expr),
                 operator?,
-                Box::new(right),
+                self.add_expr(right),
                 token.line_number,
             ))
         }
@@ -176,7 +626,7 @@ impl<'a> Parser<'a> {
     }
 
     fn comparison(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.term()?;
+        let mut expr = self.bit_or()?;
 
         while self.matched(&[
             TokenType::Greater,
@@ -196,11 +646,59 @@ impl<'a> Parser<'a> {
                 )),
             };
 
-            let right = self.term()?;
+            let right = self.bit_or()?;
             expr = Expr::Binary(Binary::new(
-                Box::new(expr),
+                self.node_id(),
+                self.add_expr(expr),
                 operator?,
-                Box::new(right),
+                self.add_expr(right),
+                token.line_number,
+            ))
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_or(&mut self) -> ParseResult<Expr> {
+        self.left_associative(&[(TokenType::Pipe, BinaryOp::BitOr)], Self::bit_xor)
+    }
+
+    fn bit_xor(&mut self) -> ParseResult<Expr> {
+        self.left_associative(&[(TokenType::Caret, BinaryOp::BitXor)], Self::bit_and)
+    }
+
+    fn bit_and(&mut self) -> ParseResult<Expr> {
+        self.left_associative(&[(TokenType::Ampersand, BinaryOp::BitAnd)], Self::shift)
+    }
+
+    fn shift(&mut self) -> ParseResult<Expr> {
+        self.left_associative(
+            &[
+                (TokenType::LessLess, BinaryOp::ShiftLeft),
+                (TokenType::GreaterGreater, BinaryOp::ShiftRight),
+            ],
+            Self::term,
+        )
+    }
+
+    fn left_associative(
+        &mut self,
+        operators: &[(TokenType, BinaryOp)],
+        operand: fn(&mut Self) -> ParseResult<Expr>,
+    ) -> ParseResult<Expr> {
+        let mut expr = operand(self)?;
+
+        while let Some((_, operator)) = operators
+            .iter()
+            .find(|(token_type, _)| self.check(token_type))
+        {
+            let token = self.advance();
+            let right = operand(self)?;
+            expr = Expr::Binary(Binary::new(
+                self.node_id(),
+                self.add_expr(expr),
+                operator.clone(),
+                self.add_expr(right),
                 token.line_number,
             ))
         }
@@ -223,9 +721,10 @@ impl<'a> Parser<'a> {
             };
             let right = self.factor()?;
             expr = Expr::Binary(Binary::new(
-                Box::new(expr),
+                self.node_id(),
+                self.add_expr(expr),
                 operator?,
-                Box::new(right),
+                self.add_expr(right),
                 token.line_number,
             ))
         }
@@ -236,21 +735,29 @@ impl<'a> Parser<'a> {
     fn factor(&mut self) -> ParseResult<Expr> {
         let mut expr = self.unary()?;
 
-        while self.matched(&[TokenType::Slash, TokenType::Star]) {
+        while self.matched(&[
+            TokenType::Slash,
+            TokenType::Star,
+            TokenType::Percent,
+            TokenType::TildeSlash,
+        ]) {
             let token = self.previous();
             let right = self.unary()?;
             let operator: Result<BinaryOp, ParseError> = match token.token_type {
                 TokenType::Slash => Ok(BinaryOp::Div),
                 TokenType::Star => Ok(BinaryOp::Mul),
+                TokenType::Percent => Ok(BinaryOp::Mod),
+                TokenType::TildeSlash => Ok(BinaryOp::IntDiv),
                 _ => Err(ParseError::new(
                     token.clone(),
                     "If you're here, you messed up",
                 )),
             };
             expr = Expr::Binary(Binary::new(
-                Box::new(expr),
+                self.node_id(),
+                self.add_expr(expr),
                 operator?,
-                Box::new(right),
+                self.add_expr(right),
                 token.line_number,
             ))
         }
@@ -258,18 +765,32 @@ impl<'a> Parser<'a> {
     }
 
     fn unary(&mut self) -> ParseResult<Expr> {
-        if self.matched(&[TokenType::Bang, TokenType::Minus]) {
+        if self.matched(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+            let operator = self.previous();
+            let target = self.unary()?;
+            return self.increment(target, operator, true);
+        }
+
+        if self.matched(&[TokenType::Bang, TokenType::Minus, TokenType::Tilde]) {
             let operator = self.previous();
             let right = self.unary()?;
             return match operator.token_type {
                 TokenType::Bang => Ok(Expr::Unary(Unary::new(
+                    self.node_id(),
                     UnaryOp::Bang,
-                    Box::new(right),
+                    self.add_expr(right),
                     operator.line_number,
                 ))),
                 TokenType::Minus => Ok(Expr::Unary(Unary::new(
+                    self.node_id(),
                     UnaryOp::Minus,
-                    Box::new(right),
+                    self.add_expr(right),
+                    operator.line_number,
+                ))),
+                TokenType::Tilde => Ok(Expr::Unary(Unary::new(
+                    self.node_id(),
+                    UnaryOp::BitNot,
+                    self.add_expr(right),
                     operator.line_number,
                 ))),
                 _ => Err(ParseError::new(
@@ -279,7 +800,252 @@ impl<'a> Parser<'a> {
             };
         }
 
-        self.primary()
+        self.power()
+    }
+
+    fn power(&mut self) -> ParseResult<Expr> {
+        let expr = self.postfix()?;
+
+        if self.matched(&[TokenType::StarStar]) {
+            let token = self.previous();
+            let right = self.unary()?;
+            return Ok(Expr::Binary(Binary::new(
+                self.node_id(),
+                self.add_expr(expr),
+                BinaryOp::Pow,
+                self.add_expr(right),
+                token.line_number,
+            )));
+        }
+
+        Ok(expr)
+    }
+
+    fn postfix(&mut self) -> ParseResult<Expr> {
+        let expr = self.call()?;
+
+        if self.matched(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+            let operator = self.previous();
+            return self.increment(expr, operator, false);
+        }
+
+        Ok(expr)
+    }
+
+    fn call(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.matched(&[TokenType::LeftParen]) {
+                expr = self.finish_call(expr)?;
+            } else if self.matched(&[TokenType::Dot]) {
+                let dot = self.previous();
+                let name = self.consume_identifier()?;
+                expr = Expr::Get(Get::new(
+                    self.node_id(),
+                    self.add_expr(expr),
+                    name,
+                    dot.line_number,
+                ));
+            } else if self.matched(&[TokenType::LeftBracket]) {
+                let bracket = self.previous();
+                let index = self.expression()?;
+                self.consume(&TokenType::RightBracket, "Expect ']' after index.")?;
+                expr = Expr::Index(Index::new(
+                    self.node_id(),
+                    self.add_expr(expr),
+                    self.add_expr(index),
+                    bracket.line_number,
+                ));
+            } else {
+                break;
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> ParseResult<Expr> {
+        let paren = self.previous();
+        let mut arguments = vec![];
+        let mut named_arguments: Vec<(Symbol, ExprId)> = vec![];
+
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                if self.is_named_argument() {
+                    let name_token = self.peek();
+                    let name = self.consume_identifier()?;
+                    if named_arguments.iter().any(|(other, _)| *other == name) {
+                        return Err(ParseError::new(
+                            name_token.clone(),
+                            format!("Duplicate named argument \"{}\".", name).as_str(),
+                        ));
+                    }
+                    self.consume(&TokenType::Colon, "Expect ':' after argument name.")?;
+                    let value = self.bound_expression(&name)?;
+                    named_arguments.push((name, value));
+                } else if !named_arguments.is_empty() {
+                    return Err(ParseError::new(
+                        self.peek().clone(),
+                        "Positional arguments must come before named arguments.",
+                    ));
+                } else {
+                    arguments.push(self.child_expression()?);
+                }
+
+                if !self.matched(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(&TokenType::RightParen, "Expect ')' after arguments.")?;
+
+        Ok(Expr::Call(Call::new(
+            self.node_id(),
+            self.add_expr(callee),
+            arguments,
+            named_arguments,
+            paren.line_number,
+        )))
+    }
+
+    fn lambda(&mut self, arrow: bool) -> ParseResult<Expr> {
+        let keyword = self.previous();
+        if !arrow {
+            self.consume(&TokenType::LeftParen, "Expect '(' after 'fun'.")?;
+        }
+
+        let mut params: Vec<Parameter> = vec![];
+        let mut rest = None;
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                if self.matched(&[TokenType::Ellipsis]) {
+                    rest = Some(self.consume_identifier()?);
+                    if !self.check(&TokenType::RightParen) {
+                        return Err(ParseError::new(
+                            self.peek().clone(),
+                            "Rest parameter must be last.",
+                        ));
+                    }
+                    break;
+                }
+
+                let name_token = self.peek();
+                let name = self.consume_identifier()?;
+                if params.iter().any(|param| param.name == name) {
+                    return Err(ParseError::new(
+                        name_token.clone(),
+                        format!("Duplicate parameter \"{}\".", name).as_str(),
+                    ));
+                }
+                let default = if self.matched(&[TokenType::Equal]) {
+                    Some(self.bound_expression(&name)?)
+                } else {
+                    None
+                };
+                params.push(Parameter { name, default });
+
+                if !self.matched(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(&TokenType::RightParen, "Expect ')' after parameters.")?;
+
+        // Loops outside the function body can't be broken out of from inside it
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        self.function_depth += 1;
+        let names = params
+            .iter()
+            .map(|param| &param.name)
+            .chain(&rest)
+            .collect::<Vec<_>>();
+        let body = self.scoped(&names, |parser| {
+            if arrow {
+                parser
+                    .consume(&TokenType::Arrow, "Expect '=>' after parameters.")
+                    .and_then(|_| parser.child_expression())
+                    .map(FunctionBody::Expression)
+            } else {
+                parser
+                    .consume(&TokenType::LeftBrace, "Expect '{' before function body.")
+                    .and_then(|_| parser.block())
+                    .map(FunctionBody::Block)
+            }
+        });
+        self.function_depth -= 1;
+        self.loop_depth = loop_depth;
+
+        Ok(Expr::Lambda(Lambda::new(
+            self.node_id(),
+            Rc::new(FunctionDecl {
+                name: None,
+                params,
+                rest,
+                body: body?,
+            }),
+            keyword.line_number,
+        )))
+    }
+
+    fn is_named_argument(&mut self) -> bool {
+        self.skip_whitespace();
+        if !matches!(self.peek().token_type, TokenType::Ident(_)) {
+            return false;
+        }
+
+        self.peek_next().token_type == TokenType::Colon
+    }
+
+    fn is_arrow_lambda(&mut self) -> bool {
+        self.skip_whitespace();
+        let Some(closing) = self.closing_parens[self.current] else {
+            return false;
+        };
+
+        self.tokens[closing + 1..]
+            .iter()
+            .find(|token| !Self::is_whitespace(&token.token_type))
+            .is_some_and(|token| token.token_type == TokenType::Arrow)
+    }
+
+    fn map_literal(&mut self) -> ParseResult<Expr> {
+        let brace = self.previous();
+        let mut entries = vec![];
+
+        if !self.check(&TokenType::RightBrace) {
+            loop {
+                let key = self.child_expression()?;
+                self.consume(&TokenType::Colon, "Expect ':' after map key.")?;
+                let value = self.child_expression()?;
+                entries.push((key, value));
+                if !self.matched(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(&TokenType::RightBrace, "Expect '}' after map entries.")?;
+        Ok(Expr::Map(Map::new(
+            self.node_id(),
+            entries,
+            brace.line_number,
+        )))
+    }
+
+    fn comma_separated(&mut self, terminator: &TokenType) -> ParseResult<Vec<ExprId>> {
+        let mut exprs = vec![];
+
+        if !self.check(terminator) {
+            loop {
+                exprs.push(self.child_expression()?);
+                if !self.matched(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        Ok(exprs)
     }
 
     fn primary(&mut self) -> ParseResult<Expr> {
@@ -288,6 +1054,7 @@ impl<'a> Parser<'a> {
         if self.matched(&[TokenType::Keyword(Keyword::False)]) {
             let token = self.previous();
             return Ok(Expr::Literal(Literal::new(
+                self.node_id(),
                 LiteralType::Bool { value: false },
                 token.line_number,
             )));
@@ -295,6 +1062,7 @@ impl<'a> Parser<'a> {
 
         if self.matched(&[TokenType::Keyword(Keyword::True)]) {
             return Ok(Expr::Literal(Literal::new(
+                self.node_id(),
                 LiteralType::Bool { value: true },
                 token.line_number,
             )));
@@ -302,15 +1070,30 @@ impl<'a> Parser<'a> {
 
         if self.matched(&[TokenType::Keyword(Keyword::Nil)]) {
             return Ok(Expr::Literal(Literal::new(
+                self.node_id(),
                 LiteralType::Nil,
                 token.line_number,
             )));
         }
 
-        if let TokenType::Number { lexeme: _, literal } = &token.token_type {
+        if let TokenType::Number { lexeme, literal } = &token.token_type {
             self.advance();
+            let literal_type = if lexeme.contains('.') {
+                LiteralType::Float { value: *literal }
+            } else {
+                match lexeme.parse::<i64>() {
+                    Ok(value) => LiteralType::Integer { value },
+                    Err(_) => {
+                        return Err(ParseError::new(
+                            token.clone(),
+                            "Integer literal is too large.",
+                        ))
+                    }
+                }
+            };
             return Ok(Expr::Literal(Literal::new(
-                LiteralType::Number { value: *literal },
+                self.node_id(),
+                literal_type,
                 token.line_number,
             )));
         }
@@ -318,6 +1101,7 @@ impl<'a> Parser<'a> {
         if let TokenType::String(value) = &token.token_type {
             self.advance();
             return Ok(Expr::Literal(Literal::new(
+                self.node_id(),
                 LiteralType::String {
                     value: value.clone(),
                 },
@@ -325,18 +1109,43 @@ impl<'a> Parser<'a> {
             )));
         }
 
+        if self.matched(&[TokenType::Keyword(Keyword::Fun)]) {
+            return self.lambda(false);
+        }
+
+        if self.check(&TokenType::LeftParen) && self.is_arrow_lambda() {
+            self.advance();
+            return self.lambda(true);
+        }
+
         if self.matched(&[TokenType::LeftParen]) {
             let expr = self.expression()?;
             self.consume(&TokenType::RightParen, "Expect ')' after expression.")?; // Match and consume ')'
             return Ok(Expr::Grouping(Grouping::new(
-                Box::new(expr),
+                self.node_id(),
+                self.add_expr(expr),
                 token.line_number,
             )));
         }
 
+        if self.matched(&[TokenType::LeftBracket]) {
+            let bracket = self.previous();
+            let elements = self.comma_separated(&TokenType::RightBracket)?;
+            self.consume(&TokenType::RightBracket, "Expect ']' after list elements.")?;
+            return Ok(Expr::List(List::new(
+                self.node_id(),
+                elements,
+                bracket.line_number,
+            )));
+        }
+
+        if self.matched(&[TokenType::LeftBrace]) {
+            return self.map_literal();
+        }
+
         if let TokenType::Ident(_) = &token.token_type {
             self.advance();
-            return Ok(Expr::Variable(Variable::new(token.clone())));
+            return Ok(Expr::Variable(Variable::new(self.node_id(), token.clone())));
         }
 
         Err(ParseError::new(
@@ -347,6 +1156,36 @@ impl<'a> Parser<'a> {
 }
 
 impl<'a> Parser<'a> {
+    fn node_id(&mut self) -> NodeId {
+        self.ast.next_id()
+    }
+
+    fn add_expr(&mut self, expr: Expr) -> ExprId {
+        self.ast.add_expr(expr)
+    }
+
+    fn add_stmt(&mut self, stmt: Stmt) -> StmtId {
+        self.ast.add_stmt(stmt)
+    }
+
+    // Parses an expression that becomes the child of another node
+    fn child_expression(&mut self) -> ParseResult<ExprId> {
+        let expr = self.expression()?;
+        Ok(self.add_expr(expr))
+    }
+
+    // Like child_expression, but a function written directly as the value is named after what it
+    // is bound to, so that stack traces can tell it apart from other functions
+    fn bound_expression(&mut self, name: &Symbol) -> ParseResult<ExprId> {
+        let mut expr = self.expression()?;
+        if let Expr::Lambda(lambda) = &mut expr {
+            if let Some(function) = Rc::get_mut(&mut lambda.function) {
+                function.name = Some(name.clone());
+            }
+        }
+        Ok(self.add_expr(expr))
+    }
+
     fn synchronize(&mut self) {
         if self.is_at_end() {
             return;
@@ -363,10 +1202,15 @@ impl<'a> Parser<'a> {
                 TokenType::Keyword(Keyword::Class)
                 | TokenType::Keyword(Keyword::Fun)
                 | TokenType::Keyword(Keyword::Var)
+                | TokenType::Keyword(Keyword::Const)
                 | TokenType::Keyword(Keyword::For)
                 | TokenType::Keyword(Keyword::If)
                 | TokenType::Keyword(Keyword::While)
                 | TokenType::Keyword(Keyword::Print)
+                | TokenType::Keyword(Keyword::Throw)
+                | TokenType::Keyword(Keyword::Try)
+                | TokenType::Keyword(Keyword::Break)
+                | TokenType::Keyword(Keyword::Continue)
                 | TokenType::Keyword(Keyword::Return) => return,
                 _ => {}
             }
@@ -387,6 +1231,30 @@ impl<'a> Parser<'a> {
         &self.tokens[self.current - 1]
     }
 
+    fn consume_identifier(&mut self) -> ParseResult<Symbol> {
+        let token = self.advance();
+        if let TokenType::Ident(name) = &token.token_type {
+            Ok(name.clone())
+        } else {
+            Err(ParseError::new(token.clone(), "Expected an identifier."))
+        }
+    }
+
+    fn consume_string(&mut self, message: &str) -> ParseResult<Arc<str>> {
+        self.skip_whitespace();
+        if let TokenType::String(value) = &self.peek().token_type {
+            self.advance();
+            Ok(value.clone())
+        } else {
+            Err(ParseError::new(self.peek().clone(), message))
+        }
+    }
+
+    fn check_contextual(&mut self, name: &str) -> bool {
+        self.skip_whitespace();
+        matches!(&self.peek().token_type, TokenType::Ident(ident) if ident.as_str() == name)
+    }
+
     fn consume(&mut self, token_type: &'a TokenType, message: &str) -> ParseResult<()> {
         self.skip_whitespace();
         if self.is_at_end() {
@@ -423,6 +1291,19 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // The token after the current one, skipping whitespace
+    fn peek_next(&self) -> &'a Token {
+        let eof = &self.tokens[self.tokens.len() - 1];
+        self.tokens
+            .get(self.current + 1..)
+            .and_then(|tokens| {
+                tokens
+                    .iter()
+                    .find(|token| !Self::is_whitespace(&token.token_type))
+            })
+            .unwrap_or(eof)
+    }
+
     fn peek(&self) -> &'a Token {
         if self.current >= self.tokens.len() {
             return &self.tokens[self.tokens.len() - 1];
@@ -437,8 +1318,12 @@ impl<'a> Parser<'a> {
     }
 
     fn matching_whitespace(&self) -> bool {
+        Self::is_whitespace(&self.peek().token_type)
+    }
+
+    fn is_whitespace(token_type: &TokenType) -> bool {
         matches!(
-            self.peek().token_type,
+            token_type,
             TokenType::Whitespace | TokenType::Tab | TokenType::NewLine | TokenType::Comment
         )
     }
@@ -0,0 +1,76 @@
+// Identifies a node in its Ast and in side tables such as NodeMap. Ids are handed out by the
+// Interpreter, so they are unique across every parse it makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+// The id of a node known to be an expression, or a statement. Only the parser makes them, as it
+// adds nodes to an Ast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExprId(pub(crate) NodeId);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StmtId(pub(crate) NodeId);
+
+impl ExprId {
+    pub fn node(&self) -> NodeId {
+        self.0
+    }
+}
+
+impl StmtId {
+    pub fn node(&self) -> NodeId {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeMap<T> {
+    values: Vec<Option<T>>,
+}
+
+impl<T> Default for NodeMap<T> {
+    fn default() -> Self {
+        Self { values: Vec::new() }
+    }
+}
+
+impl<T> NodeMap<T> {
+    pub fn with_capacity(node_count: usize) -> Self {
+        let mut values = Vec::with_capacity(node_count);
+        values.resize_with(node_count, || None);
+        Self { values }
+    }
+
+    pub fn insert(&mut self, id: NodeId, value: T) -> Option<T> {
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+        self.values[id.index()].replace(value)
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.values.get(id.index()).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.values.get_mut(id.index()).and_then(Option::as_mut)
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        self.values.get_mut(id.index()).and_then(Option::take)
+    }
+}
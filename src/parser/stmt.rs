@@ -1,10 +1,21 @@
-use crate::define_ast;
+use std::sync::Arc;
 
-use super::Expr;
+use crate::{define_ast, interner::Symbol};
+
+use super::{ExprId, StmtId};
 
 define_ast!(Stmt,
-    Print(print) { expression: Expr },
-    Expression(expression) { expression: Expr },
-    Var(var) { name: String, initializer: Option<Expr> },
-    Block(block) { statements: Vec<Stmt> },
+    Print(print) { expression: ExprId },
+    Expression(expression) { expression: ExprId },
+    Var(var) { name: Symbol, initializer: Option<ExprId>, line_number: usize },
+    Const(const_stmt) { name: Symbol, initializer: ExprId, line_number: usize },
+    Block(block) { statements: Vec<StmtId> },
+    Break(break_stmt) { line_number: usize },
+    Continue(continue_stmt) { line_number: usize },
+    Return(return_stmt) { value: Option<ExprId>, line_number: usize },
+    Throw(throw) { value: ExprId, line_number: usize },
+    Try(try_stmt) { body: Vec<StmtId>, catch_name: Option<Symbol>, catch_body: Option<Vec<StmtId>>, finally_body: Option<Vec<StmtId>>, line_number: usize },
+    Import(import) { path: Arc<str>, alias: Option<Symbol>, names: Vec<Symbol>, line_number: usize },
+    Export(export) { declaration: StmtId },
+    ForIn(for_in) { name: Symbol, iterable: ExprId, body: StmtId, line_number: usize },
 );
@@ -1,35 +1,107 @@
 use crate::parser::{
-    stmt::{Block, Expression, Print, Var, Visitor as StmtVisitor},
-    Assign, Binary, Expr, Grouping, Literal, Unary, Variable, Visitor as ExprVisitor,
+    stmt::{
+        Block, Break, Const, Continue, Export, Expression, ForIn, Import, Print, Return, Throw,
+        Try, Var, Visitor as StmtVisitor,
+    },
+    Assign, Ast, Binary, Call, CompoundAssign, Conditional, ExprId, FunctionBody, Get, Grouping,
+    Increment, Index, Lambda, List, Literal, Map, SetIndex, StmtId, Unary, Variable,
+    Visitor as ExprVisitor,
 };
 
-pub struct PrintVisitor;
+pub struct PrintVisitor<'a> {
+    ast: &'a Ast,
+}
 
-impl StmtVisitor<String> for PrintVisitor {
+impl StmtVisitor<String> for PrintVisitor<'_> {
     fn print(&mut self, expr: &Print) -> String {
-        expr.expression.accept(self)
+        self.expr(expr.expression)
     }
 
     fn expression(&mut self, expr: &Expression) -> String {
-        expr.accept(self)
+        self.expr(expr.expression)
     }
 
     fn var(&mut self, expr: &Var) -> String {
-        expr.accept(self)
+        match expr.initializer {
+            Some(initializer) => format!("(var {} {})", expr.name, self.expr(initializer)),
+            None => format!("(var {})", expr.name),
+        }
+    }
+
+    fn const_stmt(&mut self, expr: &Const) -> String {
+        format!("(const {} {})", expr.name, self.expr(expr.initializer))
     }
 
     fn block(&mut self, expr: &Block) -> String {
-        expr.accept(self)
+        format!("(block {})", self.statements(&expr.statements))
+    }
+
+    fn break_stmt(&mut self, _expr: &Break) -> String {
+        "break".to_string()
+    }
+
+    fn continue_stmt(&mut self, _expr: &Continue) -> String {
+        "continue".to_string()
+    }
+
+    fn return_stmt(&mut self, expr: &Return) -> String {
+        match expr.value {
+            Some(value) => self.parenthesize("return", &[value]),
+            None => "(return)".to_string(),
+        }
+    }
+
+    fn throw(&mut self, expr: &Throw) -> String {
+        self.parenthesize("throw", &[expr.value])
+    }
+
+    fn try_stmt(&mut self, expr: &Try) -> String {
+        let mut parts = vec![format!("(block {})", self.statements(&expr.body))];
+        if let (Some(name), Some(catch_body)) = (&expr.catch_name, &expr.catch_body) {
+            parts.push(format!("(catch {} {})", name, self.statements(catch_body)));
+        }
+        if let Some(finally_body) = &expr.finally_body {
+            parts.push(format!("(finally {})", self.statements(finally_body)));
+        }
+        format!("(try {})", parts.join(" "))
+    }
+
+    fn import(&mut self, expr: &Import) -> String {
+        match &expr.alias {
+            Some(alias) => format!("(import \"{}\" as {})", expr.path, alias),
+            None => format!(
+                "(from \"{}\" import {})",
+                expr.path,
+                expr.names
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+
+    fn export(&mut self, expr: &Export) -> String {
+        format!("(export {})", self.stmt(expr.declaration))
+    }
+
+    fn for_in(&mut self, expr: &ForIn) -> String {
+        format!(
+            "(for {} {} {})",
+            expr.name,
+            self.expr(expr.iterable),
+            self.stmt(expr.body)
+        )
     }
 }
 
-impl ExprVisitor<String> for PrintVisitor {
+impl ExprVisitor<String> for PrintVisitor<'_> {
     fn unary(&mut self, expr: &Unary) -> String {
-        self.parenthesize(&expr.operator.to_string(), &[&expr.right])
+        self.parenthesize(&expr.operator.to_string(), &[expr.right])
     }
 
     fn binary(&mut self, expr: &Binary) -> String {
-        self.parenthesize(&expr.operator.to_string(), &[&expr.left, &expr.right])
+        self.parenthesize(&expr.operator.to_string(), &[expr.left, expr.right])
     }
 
     fn literal(&mut self, expr: &Literal) -> String {
@@ -37,7 +109,7 @@ impl ExprVisitor<String> for PrintVisitor {
     }
 
     fn grouping(&mut self, expr: &Grouping) -> String {
-        self.parenthesize("group", &[&*expr.expression])
+        self.parenthesize("group", &[expr.expression])
     }
 
     fn variable(&mut self, expr: &Variable) -> String {
@@ -47,11 +119,88 @@ impl ExprVisitor<String> for PrintVisitor {
     fn assign(&mut self, _expr: &Assign) -> String {
         String::new()
     }
+
+    fn compound_assign(&mut self, expr: &CompoundAssign) -> String {
+        self.parenthesize(&format!("{}=", expr.operator), &[expr.target, expr.value])
+    }
+
+    fn increment(&mut self, expr: &Increment) -> String {
+        let name = if expr.prefix {
+            format!("{}{}", expr.operator, expr.operator)
+        } else {
+            format!("post{}{}", expr.operator, expr.operator)
+        };
+        self.parenthesize(&name, &[expr.target])
+    }
+
+    fn conditional(&mut self, expr: &Conditional) -> String {
+        self.parenthesize("?:", &[expr.condition, expr.then_branch, expr.else_branch])
+    }
+
+    fn lambda(&mut self, expr: &Lambda) -> String {
+        let mut params = expr
+            .function
+            .params
+            .iter()
+            .map(|param| match param.default {
+                Some(default) => format!("(= {} {})", param.name, self.expr(default)),
+                None => param.name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        if let Some(rest) = &expr.function.rest {
+            params.push(format!("...{}", rest));
+        }
+        let params = params.join(" ");
+        match &expr.function.body {
+            FunctionBody::Block(body) => format!("(fun ({}) {})", params, self.statements(body)),
+            FunctionBody::Expression(body) => format!("(=> ({}) {})", params, self.expr(*body)),
+        }
+    }
+
+    fn call(&mut self, expr: &Call) -> String {
+        let mut parts = vec![self.expr(expr.callee)];
+        parts.extend(expr.arguments.iter().map(|argument| self.expr(*argument)));
+        parts.extend(
+            expr.named_arguments
+                .iter()
+                .map(|(name, argument)| format!("({}: {})", name, self.expr(*argument))),
+        );
+        format!("(call {})", parts.join(" "))
+    }
+
+    fn list(&mut self, expr: &List) -> String {
+        self.parenthesize("list", &expr.elements)
+    }
+
+    fn map(&mut self, expr: &Map) -> String {
+        let exprs = expr
+            .entries
+            .iter()
+            .flat_map(|(key, value)| [*key, *value])
+            .collect::<Vec<_>>();
+        self.parenthesize("map", &exprs)
+    }
+
+    fn get(&mut self, expr: &Get) -> String {
+        format!("(. {} {})", self.expr(expr.object), expr.name)
+    }
+
+    fn index(&mut self, expr: &Index) -> String {
+        self.parenthesize("index", &[expr.object, expr.index])
+    }
+
+    fn set_index(&mut self, expr: &SetIndex) -> String {
+        self.parenthesize("set-index", &[expr.object, expr.index, expr.value])
+    }
 }
 
-impl PrintVisitor {
-    pub fn print(&mut self, expr: &Expr) -> Option<String> {
-        let to_print = expr.accept(self);
+impl<'a> PrintVisitor<'a> {
+    pub fn new(ast: &'a Ast) -> Self {
+        Self { ast }
+    }
+
+    pub fn print(&mut self, expr: ExprId) -> Option<String> {
+        let to_print = self.expr(expr);
         if to_print.is_empty() {
             None
         } else {
@@ -59,13 +208,29 @@ impl PrintVisitor {
         }
     }
 
-    fn parenthesize(&mut self, name: &str, exprs: &[&Expr]) -> String {
+    pub fn stmt(&mut self, stmt: StmtId) -> String {
+        self.ast[stmt].accept(self)
+    }
+
+    fn expr(&mut self, expr: ExprId) -> String {
+        self.ast[expr].accept(self)
+    }
+
+    fn statements(&mut self, stmts: &[StmtId]) -> String {
+        stmts
+            .iter()
+            .map(|stmt| self.stmt(*stmt))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn parenthesize(&mut self, name: &str, exprs: &[ExprId]) -> String {
         format!(
             "({} {})",
             name,
             exprs
                 .iter()
-                .map(|expr| expr.accept(self))
+                .map(|expr| self.expr(*expr))
                 .collect::<Vec<_>>()
                 .join(" ")
         )
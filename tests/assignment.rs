@@ -0,0 +1,47 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn compound_assignment_and_increments_update_variables() {
+    let source = "
+var x = 1;
+x += 2;
+print x;
+x -= 1;
+x *= 3;
+print x;
+x /= 4;
+print x;
+var y = 5;
+print y++;
+print ++y;
+print y--;
+print --y;
+var s = \"a\";
+s += \"b\";
+print s;
+";
+    assert_eq!(printed(source), ["3", "6", "1.5", "5", "7", "7", "5", "ab"]);
+}
+
+#[test]
+fn only_variables_and_indices_can_be_assigned() {
+    assert_eq!(error("5++;"), "[line 1] Error: Invalid assignment target.");
+}
+
+#[test]
+fn compound_index_assignment_evaluates_the_index_once() {
+    let source = "
+var calls = 0;
+var list = [10, 20, 30];
+var next = fun () { calls += 1; return 1; };
+list[next()] += 5;
+print list;
+print calls;
+list[next()]++;
+print list;
+print calls;
+";
+    assert_eq!(printed(source), ["[10, 25, 30]", "1", "[10, 26, 30]", "2"]);
+}
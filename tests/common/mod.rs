@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use lox_interpreter::{
+    error::LoxError, interpreter::Interpreter, visitors::print_visitor::PrintVisitor,
+};
+
+pub struct Output {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: i32,
+}
+
+// A fresh directory per call, so tests running in parallel don't see each other's files
+pub fn temp_dir() -> PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "lox_test_{}_{}",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Writes each (path, contents) pair into a temporary directory and runs the first file from there
+pub fn run_files(files: &[(&str, &str)]) -> Output {
+    let dir = temp_dir();
+    for (path, source) in files {
+        fs::write(dir.join(path), source).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lox_interpreter"))
+        .arg(files[0].0)
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    Output {
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+        code: output.status.code().unwrap(),
+    }
+}
+
+pub fn run(source: &str) -> Output {
+    run_files(&[("main.lox", source)])
+}
+
+// Runs a script that should succeed and returns the lines it printed
+pub fn printed(source: &str) -> Vec<String> {
+    let output = run(source);
+    assert_eq!(output.code, 0, "script failed: {}", output.stderr);
+    output.stdout.lines().map(str::to_string).collect()
+}
+
+// Runs a script that should fail and returns the first line of its error report
+pub fn error(source: &str) -> String {
+    let output = run(source);
+    assert_ne!(output.code, 0, "script succeeded: {}", output.stdout);
+    output.stderr.lines().next().unwrap_or_default().to_string()
+}
+
+pub fn interpret(interpreter: &mut Interpreter, source: &str) -> Result<(), LoxError> {
+    let program = interpreter.parse(source)?;
+    interpreter.interpret(&program)?;
+    Ok(())
+}
+
+// Parses a script and renders each top-level statement with the AST printer
+pub fn printed_tree(source: &str) -> Vec<String> {
+    let program = Interpreter::new().parse(source).unwrap();
+    let mut visitor = PrintVisitor::new(&program.ast);
+    program
+        .statements
+        .iter()
+        .map(|stmt| visitor.stmt(*stmt))
+        .collect()
+}
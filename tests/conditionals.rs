@@ -0,0 +1,35 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn only_nil_and_false_choose_the_else_branch() {
+    assert_eq!(
+        printed("print true ? 1 : 2; print nil ? 1 : 2; print 0 ? \"zero\" : \"no\";"),
+        ["1", "2", "zero"]
+    );
+}
+
+#[test]
+fn only_the_chosen_branch_is_evaluated() {
+    assert_eq!(
+        printed("print true ? 1 : missing; print false ? missing : 2;"),
+        ["1", "2"]
+    );
+}
+
+#[test]
+fn conditionals_nest_to_the_right_below_other_operators() {
+    assert_eq!(
+        printed_tree("false ? 1 : true ? 2 : 3; 1 + 1 == 2 ? a : b;"),
+        ["(?: false 1 (?: true 2 3))", "(?: (== (+ 1 1) 2) a b)"]
+    );
+    assert_eq!(
+        printed("var x; x = 1 + 1 == 2 ? \"a\" : \"b\"; print x;"),
+        ["a"]
+    );
+    assert_eq!(
+        error("print true ? 1;"),
+        "[line 1] Error: Expect ':' after then branch of conditional expression."
+    );
+}
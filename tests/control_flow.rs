@@ -0,0 +1,46 @@
+mod common;
+
+use common::{error, printed};
+
+// The language has no `if`, so loops leave early by catching a thrown value
+#[test]
+fn break_and_continue_leave_the_innermost_loop() {
+    let source = "
+for (var i in range(0, 10)) {
+    try { i < 3 ? nil : throw_it; } catch (e) { break; }
+    print i;
+}
+for (var i in range(0, 5)) {
+    try { i % 2 == 0 ? nil : throw_it; } catch (e) { continue; }
+    print i;
+}
+for (var i in range(0, 2)) {
+    for (var j in range(0, 3)) { break; }
+    print i;
+}
+";
+    assert_eq!(printed(source), ["0", "1", "2", "0", "2", "4", "0", "1"]);
+}
+
+#[test]
+fn break_runs_finally_blocks() {
+    let source = "
+for (var i in range(0, 3)) {
+    try { break; } finally { print \"finally\"; }
+}
+print \"done\";
+";
+    assert_eq!(printed(source), ["finally", "done"]);
+}
+
+#[test]
+fn break_and_continue_outside_a_loop_are_parse_errors() {
+    assert_eq!(
+        error("break;"),
+        "[line 1] Error: Can't use 'break' outside of a loop."
+    );
+    assert_eq!(
+        error("for (var i in [1]) {\n    var f = fun () { continue; };\n}"),
+        "[line 2] Error: Can't use 'continue' outside of a loop."
+    );
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{error, printed, run};
+
+#[test]
+fn constants_cannot_be_assigned() {
+    assert_eq!(
+        error("const x = 1;\nx = 2;\n"),
+        "[line 2] Error: Cannot assign to constant \"x\"."
+    );
+    assert_eq!(
+        error("const x = 1;\nx += 2;\n"),
+        "[line 2] Error: Cannot assign to constant \"x\"."
+    );
+    assert_eq!(
+        error("const x = 1;\nx++;\n"),
+        "[line 2] Error: Cannot assign to constant \"x\"."
+    );
+}
+
+#[test]
+fn constants_cannot_be_redeclared_in_the_same_scope() {
+    assert_eq!(
+        error("const x = 1;\nvar x = 2;\nx = 3;\nprint x;\n"),
+        "[line 2] Error: Cannot redeclare constant \"x\"."
+    );
+    assert_eq!(
+        error("const x = 1;\nconst x = 2;\n"),
+        "[line 2] Error: Cannot redeclare constant \"x\"."
+    );
+}
+
+#[test]
+fn constants_can_be_shadowed_in_an_inner_scope() {
+    assert_eq!(
+        printed("const x = 1;\n{ var x = 2; x = 3; print x; }\nprint x;\n"),
+        ["3", "1"]
+    );
+}
+
+#[test]
+fn vars_can_be_redeclared() {
+    assert_eq!(
+        printed("var x = 1;\nvar x = 2;\nconst x = 3;\nprint x;\n"),
+        ["3"]
+    );
+}
+
+#[test]
+fn scripts_can_declare_names_used_by_builtins() {
+    assert_eq!(
+        printed("var pi = 3;\nprint pi;\nprint len([1]);\n"),
+        ["3", "1"]
+    );
+}
+
+// A function declared before the constant assigns it, which only the interpreter can catch
+#[test]
+fn const_errors_can_be_caught() {
+    let source = "
+var set = fun () { x = 2; };
+const x = 1;
+try { set(); } catch (e) { print e[\"message\"]; }
+print x;
+";
+    assert_eq!(printed(source), ["Cannot assign to constant \"x\".", "1"]);
+}
+
+#[test]
+fn visible_constants_are_rejected_before_anything_runs() {
+    let output = run("print \"ran\";\nconst x = 1;\nvar f = fun () { { x += 1; } };\n");
+    assert_eq!(output.stdout, "");
+    assert_eq!(
+        output.stderr,
+        "[line 3] Error: Cannot assign to constant \"x\".\n"
+    );
+}
+
+#[test]
+fn inner_declarations_hide_constants_from_the_check() {
+    let source = "
+const x = 1;
+var f = fun (x) { x = 2; return x; };
+for (var x in [1]) { x = 3; }
+try { throw 1; } catch (x) { x = 4; }
+{ var x = 5; x++; }
+print f(0);
+";
+    assert_eq!(printed(source), ["2"]);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn catch_and_finally_run_in_order_and_rethrow() {
+    let source = "
+var log = [];
+var f = fun () {
+    try { push(log, \"try\"); throw \"boom\"; push(log, \"never\"); }
+    catch (e) { push(log, \"catch \" + e); throw e + \"!\"; }
+    finally { push(log, \"finally\"); }
+};
+try { f(); } catch (e) { push(log, \"outer \" + e); }
+print log;
+";
+    assert_eq!(printed(source), ["[try, catch boom, finally, outer boom!]"]);
+}
+
+#[test]
+fn finally_runs_on_return_and_uncaught_throws() {
+    let source = "
+var f = fun () { try { return 1; } finally { print \"cleanup\"; } };
+print f();
+try {
+    try { throw 2; } finally { print \"inner finally\"; }
+} catch (e) {
+    print e;
+}
+";
+    assert_eq!(printed(source), ["cleanup", "1", "inner finally", "2"]);
+}
+
+#[test]
+fn runtime_errors_are_caught_as_maps() {
+    let source = "
+try { 1 / nil; } catch (e) {
+    print e[\"message\"];
+    print e[\"line\"];
+}
+try { throw [1, 2]; } catch (e) { print e[1]; }
+";
+    assert_eq!(printed(source), ["Operands must be numbers.", "2", "2"]);
+}
+
+#[test]
+fn uncaught_throws_are_reported() {
+    assert_eq!(error("throw \"x\";"), "[line 1] Error: Uncaught x");
+    assert_eq!(
+        error("try { print 1; }"),
+        "[line 1] Error: Expect 'catch' or 'finally' after try block."
+    );
+}
+
+#[test]
+fn print_visitor_renders_try_bodies() {
+    assert_eq!(
+        printed_tree("try { print 1; } catch (e) { print e; } finally { print 2; }"),
+        ["(try (block 1) (catch e e) (finally 2))"]
+    );
+}
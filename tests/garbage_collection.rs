@@ -0,0 +1,98 @@
+mod common;
+
+use common::interpret;
+use lox_interpreter::interpreter::Interpreter;
+
+// Scripts check their own results, since a library run has nowhere to print to
+const ASSERT: &str = "
+var assert = fun (ok, message) {
+    var fail = fun () { throw message; };
+    (ok ? fun () {} : fail)();
+};
+";
+
+fn interpreter() -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpret(&mut interpreter, ASSERT).unwrap();
+    interpreter
+}
+
+#[test]
+fn dead_cycles_are_collected() {
+    let mut interpreter = interpreter();
+    interpret(
+        &mut interpreter,
+        "
+        for (var i in range(0, 100)) {
+            var list = [];
+            push(list, list);
+            var map = {};
+            map[\"self\"] = map;
+            var f = fun () { return f; };
+        }
+        ",
+    )
+    .unwrap();
+
+    assert!(interpreter.collect_garbage() >= 300);
+    assert_eq!(interpreter.collect_garbage(), 0);
+}
+
+#[test]
+fn live_cycles_survive_a_collection() {
+    let mut interpreter = interpreter();
+    interpret(
+        &mut interpreter,
+        "
+        var keep = [1];
+        push(keep, keep);
+        var counter = fun () {
+            var count = 0;
+            var next = fun () { count = count + 1; return count; };
+            return next;
+        }();
+        counter();
+        ",
+    )
+    .unwrap();
+
+    interpreter.collect_garbage();
+    interpret(
+        &mut interpreter,
+        "
+        assert(keep[1][1][0] == 1, \"list lost its contents\");
+        assert(counter() == 2, \"closure lost its scope\");
+        ",
+    )
+    .unwrap();
+}
+
+#[test]
+fn collection_during_a_call_keeps_temporaries() {
+    let mut interpreter = interpreter();
+    interpreter.set_gc_stress(true);
+    interpret(
+        &mut interpreter,
+        "
+        var make = fun (n) {
+            var node = {\"value\": n, \"children\": []};
+            node[\"self\"] = node;
+            for (var i in range(0, n)) { push(node[\"children\"], make(n - 1)); }
+            return node;
+        };
+        var count = fun (node) {
+            var total = 1;
+            for (var child in node[\"children\"]) { total = total + count(child); }
+            return total;
+        };
+        assert(count(make(4)) == 65, \"tree lost nodes\");
+
+        var adders = [];
+        for (var n in range(0, 5)) { push(adders, (x) => x + n); }
+        assert(adders[3](10) == 13, \"closure lost its binding\");
+        var copy = json.parse(json.stringify([[1, 2], {\"a\": [3]}]));
+        assert(copy[1][\"a\"][0] == 3, \"json lost data\");
+        ",
+    )
+    .unwrap();
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::interpret;
+use lox_interpreter::{
+    interner::Interner,
+    interpreter::Interpreter,
+    lexer::{Lexer, TokenType},
+};
+
+#[test]
+fn equal_identifiers_and_literals_share_one_string() {
+    let mut interner = Interner::default();
+    let tokens = Lexer::new("name \"text\" name \"text\" other", &mut interner)
+        .lex()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .filter(|token_type| matches!(token_type, TokenType::Ident(_) | TokenType::String(_)))
+        .collect::<Vec<_>>();
+
+    match tokens.as_slice() {
+        [TokenType::Ident(first), TokenType::String(text), TokenType::Ident(second), TokenType::String(same_text), TokenType::Ident(other)] =>
+        {
+            assert_eq!(first, second);
+            assert_ne!(first, other);
+            assert!(Arc::ptr_eq(text, same_text));
+        }
+        tokens => panic!("unexpected tokens: {:?}", tokens),
+    }
+}
+
+#[test]
+fn strings_of_finished_scripts_are_reclaimed() {
+    let mut interpreter = Interpreter::new();
+    interpret(&mut interpreter, "var keep = \"kept\";").unwrap();
+    let baseline = interpreter.interned_strings();
+
+    for i in 0..1000 {
+        let source = format!("{{ var local_{} = len(\"literal {}\"); }}", i, i);
+        let program = interpreter.parse(&source).unwrap();
+        interpreter.interpret(&program).unwrap();
+    }
+
+    // Nothing from those scripts outlives them, so their names and literals don't accumulate
+    assert!(interpreter.interned_strings() <= baseline + 2);
+    interpret(&mut interpreter, "len(keep);").unwrap();
+}
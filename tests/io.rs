@@ -0,0 +1,125 @@
+mod common;
+
+use std::fs;
+
+use common::{interpret, printed, temp_dir};
+use lox_interpreter::{error::LoxError, interpreter::Interpreter};
+
+// The first line of a failed run's report
+fn first_error(interpreter: &mut Interpreter, source: &str) -> String {
+    let err = interpret(interpreter, source).unwrap_err().to_string();
+    err.lines().next().unwrap_or_default().to_string()
+}
+
+#[test]
+fn embedded_interpreters_start_without_filesystem_access() {
+    let dir = temp_dir();
+    let file = dir.join("data.txt");
+    fs::write(&file, "secret").unwrap();
+    fs::write(dir.join("lib.lox"), "export var name = \"lib\";").unwrap();
+    let file = file.display();
+    let lib = dir.join("lib.lox");
+    let lib = lib.display();
+
+    let mut interpreter = Interpreter::new();
+    for native in ["readFile", "exists", "listDir"] {
+        assert_eq!(
+            first_error(&mut interpreter, &format!("{}(\"{}\");", native, file)),
+            format!(
+                "[line 1] Error: {}() is unavailable, filesystem access is disabled.",
+                native
+            )
+        );
+    }
+    for native in ["writeFile", "appendFile"] {
+        assert_eq!(
+            first_error(
+                &mut interpreter,
+                &format!("{}(\"{}\", \"x\");", native, file)
+            ),
+            format!(
+                "[line 1] Error: {}() is unavailable, filesystem access is disabled.",
+                native
+            )
+        );
+    }
+    assert_eq!(
+        first_error(&mut interpreter, &format!("import \"{}\" as lib;", lib)),
+        format!(
+            "[line 1] Error: Could not load module \"{}\": filesystem access is disabled.",
+            lib
+        )
+    );
+
+    assert_eq!(fs::read_to_string(dir.join("data.txt")).unwrap(), "secret");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn enabling_filesystem_access_allows_files_and_imports() {
+    let dir = temp_dir();
+    fs::write(dir.join("lib.lox"), "export var name = \"lib\";").unwrap();
+    let file = dir.join("data.txt");
+    let file = file.display();
+    let lib = dir.join("lib.lox");
+    let lib = lib.display();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_filesystem_access(true);
+    interpret(
+        &mut interpreter,
+        &format!(
+            "
+            writeFile(\"{file}\", \"a\");
+            appendFile(\"{file}\", \"b\");
+            import \"{lib}\" as lib;
+            var contents = readFile(\"{file}\") + lib.name;
+            var files = listDir(\"{dir}\");
+            ",
+            file = file,
+            lib = lib,
+            dir = dir.display()
+        ),
+    )
+    .unwrap();
+    assert_eq!(fs::read_to_string(dir.join("data.txt")).unwrap(), "ab");
+
+    // Turning it back off also refuses imports that were resolved while it was on
+    let program = interpreter
+        .parse(&format!("import \"{}\" as again;", lib))
+        .unwrap();
+    interpreter.interpret(&program).unwrap();
+    interpreter.set_filesystem_access(false);
+    assert_eq!(
+        LoxError::from(interpreter.interpret(&program).unwrap_err())
+            .to_string()
+            .lines()
+            .next()
+            .unwrap(),
+        format!(
+            "[line 1] Error: Could not load module \"{}\": filesystem access is disabled.",
+            lib
+        )
+    );
+    assert_eq!(
+        first_error(&mut interpreter, &format!("exists(\"{}\");", file)),
+        "[line 1] Error: exists() is unavailable, filesystem access is disabled."
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn scripts_run_from_the_command_line_can_use_files() {
+    let source = "
+writeFile(\"out.txt\", \"héllo\");
+appendFile(\"out.txt\", \" wörld\");
+print readFile(\"out.txt\");
+print exists(\"out.txt\");
+print exists(\"missing.txt\");
+print listDir(\".\");
+";
+    assert_eq!(
+        printed(source),
+        ["héllo wörld", "true", "false", "[main.lox, out.txt]"]
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{printed, run_files};
+
+#[test]
+fn values_round_trip() {
+    let source = "
+var value = {\"a\": [1, 2.5, \"x\", nil, true], \"b\": {\"c\": -3}};
+var encoded = json.stringify(value);
+print encoded;
+print json.stringify(json.parse(encoded)) == encoded;
+print json.parse(\"[1, 2.0, 1e3, -0.5]\");
+";
+    assert_eq!(
+        printed(source),
+        [
+            r#"{"a":[1,2.5,"x",null,true],"b":{"c":-3}}"#,
+            "true",
+            "[1, 2.0, 1000.0, -0.5]",
+        ]
+    );
+}
+
+#[test]
+fn strings_round_trip_through_escapes() {
+    let output = run_files(&[
+        (
+            "main.lox",
+            "var text = json.parse(readFile(\"data.json\"));\nprint len(text);\nprint json.stringify(text);\n",
+        ),
+        ("data.json", r#""tab\t quote\" é 😀""#),
+    ]);
+    assert_eq!(output.stderr, "");
+    assert_eq!(output.stdout, "15\n\"tab\\t quote\\\" é 😀\"\n");
+}
+
+#[test]
+fn stringify_indents() {
+    assert_eq!(
+        printed("print json.stringify({\"k\": [1, {}], \"e\": []}, \"  \");"),
+        [
+            "{",
+            "  \"k\": [",
+            "    1,",
+            "    {}",
+            "  ],",
+            "  \"e\": []",
+            "}"
+        ]
+    );
+}
+
+#[test]
+fn parse_errors_report_their_position() {
+    let source = "
+var attempt = fun (text) { try { json.parse(text); } catch (e) { print e[\"message\"]; } };
+attempt(\"[1, 2\");
+attempt(\"{'a': 1}\");
+attempt(\"[1,
+  x]\");
+attempt(\"[1] 2\");
+";
+    assert_eq!(
+        printed(source),
+        [
+            "json.parse() unexpected end of input at line 1, column 6.",
+            "json.parse() expected string key at line 1, column 2.",
+            "json.parse() unexpected 'x' at line 2, column 3.",
+            "json.parse() unexpected '2' after JSON value at line 1, column 5.",
+        ]
+    );
+}
+
+#[test]
+fn stringify_rejects_values_without_a_json_form() {
+    let source = "
+var attempt = fun (value) { try { json.stringify(value); } catch (e) { print e[\"message\"]; } };
+attempt(fun () {});
+attempt(range(0, 3));
+var cyclic = [];
+push(cyclic, cyclic);
+attempt(cyclic);
+";
+    assert_eq!(
+        printed(source),
+        [
+            "json.stringify() can't encode <fn lambda>.",
+            "json.stringify() can't encode range(0, 3, 1).",
+            "json.stringify() can't encode a cyclic structure.",
+        ]
+    );
+}
+
+#[test]
+fn nesting_depth_is_limited_both_ways() {
+    let source = "
+var deep = [];
+var innermost = deep;
+for (var i in range(0, 100000)) { var next = []; push(innermost, next); innermost = next; }
+try { json.stringify(deep); } catch (e) { print e[\"message\"]; }
+var make = fun () { return ({\"toJSON\": make}); };
+try { json.stringify(make()); } catch (e) { print e[\"message\"]; }
+try { json.parse(repeat(\"[\", 100000)); } catch (e) { print e[\"message\"]; }
+";
+    let lines = printed(source);
+    assert_eq!(lines[0], "json.stringify() nesting too deep.");
+    assert_eq!(lines[1], "json.stringify() nesting too deep.");
+    assert!(
+        lines[2].starts_with("json.parse() nesting too deep"),
+        "{}",
+        lines[2]
+    );
+}
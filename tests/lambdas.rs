@@ -0,0 +1,42 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn arrow_lambdas_and_groupings_are_told_apart() {
+    let source = "
+var inc = (x) => x + 1;
+print inc(1);
+print ((x) => x * 2)(3);
+print (1 + 2) * 3;
+print (() => 7)();
+var x = 4;
+print ((x));
+var add = (a, b)
+    => a + b;
+print add(1, 2);
+";
+    assert_eq!(printed(source), ["2", "6", "9", "7", "4", "3"]);
+    assert_eq!(
+        printed_tree("(x) => (x); ((x));"),
+        ["(=> (x) (group x))", "(group (group x))"]
+    );
+}
+
+#[test]
+fn lambdas_close_over_their_scope() {
+    let source = "
+var adder = (x) => (y) => x + y;
+print adder(1)(2);
+print (fun (a) { return a * 2; })(5);
+";
+    assert_eq!(printed(source), ["3", "10"]);
+}
+
+#[test]
+fn arrow_parameters_must_be_names() {
+    assert_eq!(
+        error("(1 + 2) => 3;"),
+        "[line 1] Error: Expected an identifier."
+    );
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::interpret;
+use lox_interpreter::{
+    error::LoxError,
+    interpreter::{limits::InterpreterLimits, Interpreter, RuntimeError, RuntimeErrorKind},
+};
+
+const ENDLESS: &str = "var n = 0;\nfor (var i in range(0, 9223372036854775807)) { n += 1; }\n";
+
+// A handful of lists that print and encode as 2^26 elements, since each is stored twice in the next
+const SHARED: &str = "var a=[1]; for (var i in range(0,26)) { a=[a,a]; } ";
+
+fn runtime_error(result: Result<(), LoxError>) -> RuntimeError {
+    match result {
+        Err(LoxError::RuntimeError(err)) => err,
+        Err(err) => panic!("expected a runtime error, got {}", err),
+        Ok(()) => panic!("expected a runtime error"),
+    }
+}
+
+fn limited(limits: InterpreterLimits) -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_limits(limits);
+    interpreter
+}
+
+#[test]
+fn step_limit() {
+    let mut interpreter = limited(InterpreterLimits {
+        max_steps: Some(10_000),
+        ..Default::default()
+    });
+    let err = runtime_error(interpret(&mut interpreter, ENDLESS));
+    assert_eq!(err.kind, RuntimeErrorKind::StepLimitExceeded);
+    assert_eq!(err.line_number, 2);
+}
+
+#[test]
+fn timeout() {
+    let mut interpreter = limited(InterpreterLimits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    let err = runtime_error(interpret(&mut interpreter, ENDLESS));
+    assert_eq!(err.kind, RuntimeErrorKind::Timeout);
+}
+
+#[test]
+fn memory_limit() {
+    let mut interpreter = limited(InterpreterLimits {
+        max_allocated_bytes: Some(1 << 20),
+        ..Default::default()
+    });
+    let err = runtime_error(interpret(
+        &mut interpreter,
+        "var s = repeat(\"a\", 2000000);",
+    ));
+    assert_eq!(err.kind, RuntimeErrorKind::MemoryLimitExceeded);
+
+    let err = runtime_error(interpret(
+        &mut interpreter,
+        "var list = [];\nfor (var i in range(0, 1000000)) { push(list, i); }\n",
+    ));
+    assert_eq!(err.kind, RuntimeErrorKind::MemoryLimitExceeded);
+}
+
+#[test]
+fn shared_values_are_charged_as_they_are_written_out() {
+    for statement in ["json.stringify(a);", "print a;"] {
+        let mut interpreter = limited(InterpreterLimits {
+            timeout: Some(Duration::from_millis(200)),
+            max_allocated_bytes: Some(1 << 20),
+            ..Default::default()
+        });
+        let started = Instant::now();
+        let err = runtime_error(interpret(
+            &mut interpreter,
+            &format!("{}{}", SHARED, statement),
+        ));
+        // Which limit comes first depends on how fast the build is
+        assert!(matches!(
+            err.kind,
+            RuntimeErrorKind::Timeout | RuntimeErrorKind::MemoryLimitExceeded
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let mut interpreter = limited(InterpreterLimits {
+            max_steps: Some(100_000),
+            ..Default::default()
+        });
+        let err = runtime_error(interpret(
+            &mut interpreter,
+            &format!("{}{}", SHARED, statement),
+        ));
+        assert_eq!(err.kind, RuntimeErrorKind::StepLimitExceeded);
+    }
+}
+
+#[test]
+fn cancellation() {
+    let mut interpreter = Interpreter::new();
+    let handle = interpreter.cancellation_handle();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+    });
+    let err = runtime_error(interpret(&mut interpreter, ENDLESS));
+    canceller.join().unwrap();
+    assert_eq!(err.kind, RuntimeErrorKind::Cancelled);
+}
+
+// A cancel that lands just before interpret() is called still stops that run, and only that run
+#[test]
+fn cancelling_between_runs_stops_only_the_next_run() {
+    let mut interpreter = Interpreter::new();
+    interpreter.cancellation_handle().cancel();
+    let err = runtime_error(interpret(&mut interpreter, "var x = 1;"));
+    assert_eq!(err.kind, RuntimeErrorKind::Cancelled);
+    assert!(interpret(&mut interpreter, "var x = 1;").is_ok());
+}
+
+#[test]
+fn limits_are_counted_per_run() {
+    let mut interpreter = limited(InterpreterLimits {
+        max_steps: Some(1_000),
+        ..Default::default()
+    });
+    for _ in 0..5 {
+        interpret(&mut interpreter, "for (var i in range(0, 100)) {}").unwrap();
+    }
+}
+
+#[test]
+fn limits_cannot_be_caught() {
+    let mut interpreter = limited(InterpreterLimits {
+        max_steps: Some(10_000),
+        ..Default::default()
+    });
+    let source = format!("try {{\n{}}} catch (e) {{ throw \"caught\"; }}", ENDLESS);
+    let err = runtime_error(interpret(&mut interpreter, &source));
+    assert_eq!(err.kind, RuntimeErrorKind::StepLimitExceeded);
+}
+
+#[test]
+fn limits_skip_finally() {
+    let mut interpreter = limited(InterpreterLimits {
+        max_steps: Some(10_000),
+        ..Default::default()
+    });
+    let source = format!("try {{\n{}}} finally {{ throw \"finally\"; }}", ENDLESS);
+    let err = runtime_error(interpret(&mut interpreter, &source));
+    assert_eq!(err.kind, RuntimeErrorKind::StepLimitExceeded);
+
+    // A limit reached inside catch is just as final
+    let source = format!(
+        "try {{ throw 1; }} catch (e) {{\n{}}} finally {{ throw \"finally\"; }}",
+        ENDLESS
+    );
+    let err = runtime_error(interpret(&mut interpreter, &source));
+    assert_eq!(err.kind, RuntimeErrorKind::StepLimitExceeded);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn lists_are_indexed_and_assigned_in_place() {
+    let source = "
+var list = [1, \"two\", 3.0];
+print list;
+print list[1];
+print len(list);
+list[0] = 10;
+print list;
+";
+    assert_eq!(
+        printed(source),
+        ["[1, two, 3.0]", "two", "3", "[10, two, 3.0]"]
+    );
+}
+
+#[test]
+fn list_natives_modify_and_copy() {
+    let source = "
+var list = [1, 2];
+push(list, 3);
+print list;
+print pop(list);
+insert(list, 0, 0);
+print list;
+print slice(list, 1, 3);
+print list;
+";
+    assert_eq!(
+        printed(source),
+        ["[1, 2, 3]", "3", "[0, 1, 2]", "[1, 2]", "[0, 1, 2]"]
+    );
+}
+
+#[test]
+fn bad_indices_are_runtime_errors() {
+    assert_eq!(
+        error("var list = [1];\nprint list[1];"),
+        "[line 2] Error: List index out of range."
+    );
+    assert_eq!(
+        error("var list = [1];\nprint list[-1];"),
+        "[line 2] Error: List index out of range."
+    );
+    assert_eq!(
+        error("var list = [1];\nlist[\"a\"] = 2;"),
+        "[line 2] Error: List index must be an integer."
+    );
+    assert_eq!(
+        error("print 1[0];"),
+        "[line 1] Error: Only lists, maps and ranges can be indexed."
+    );
+}
+
+#[test]
+fn bad_native_arguments_are_runtime_errors() {
+    assert_eq!(error("pop([]);"), "[line 1] Error: pop() from empty list.");
+    assert_eq!(
+        error("insert([1], 3, 0);"),
+        "[line 1] Error: insert() index out of range."
+    );
+    assert_eq!(
+        error("slice([1, 2], 2, 1);"),
+        "[line 1] Error: slice() start must not be greater than end."
+    );
+    assert_eq!(
+        error("push(1, 2);"),
+        "[line 1] Error: push() expects a list as its first argument."
+    );
+}
+
+#[test]
+fn printing_a_cyclic_list_terminates() {
+    let source = "
+var list = [1];
+push(list, list);
+print list;
+";
+    assert_eq!(printed(source), ["[1, [...]]"]);
+}
+
+#[test]
+fn printing_deeply_nested_lists_is_cut_off() {
+    let source = "
+var deep = [];
+var innermost = deep;
+for (var i in range(0, 100000)) { var next = []; push(innermost, next); innermost = next; }
+print deep;
+";
+    let lines = printed(source);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].len() < 1000 && lines[0].contains("[...]"));
+}
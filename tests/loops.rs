@@ -0,0 +1,64 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn each_iteration_gets_its_own_binding() {
+    let source = "
+var closures = [];
+for (var i in range(0, 3)) { push(closures, () => i); i = i * 10; }
+print closures[0]();
+print closures[2]();
+";
+    assert_eq!(printed(source), ["0", "20"]);
+}
+
+#[test]
+fn lists_strings_and_ranges_can_be_iterated() {
+    let source = "
+var list = [1, 2];
+for (var x in list) { print x; x < 3 ? push(list, x + 2) : nil; }
+for (var c in \"hé\") print c;
+for (var x in range(0, 1, 0.25)) print x;
+";
+    assert_eq!(
+        printed(source),
+        ["1", "2", "3", "4", "h", "é", "0.0", "0.25", "0.5", "0.75"]
+    );
+}
+
+#[test]
+fn other_values_cannot_be_iterated() {
+    assert_eq!(
+        error("for (var x in 1) print x;"),
+        "[line 1] Error: Can only iterate over lists, maps, strings and ranges."
+    );
+}
+
+#[test]
+fn huge_ranges_are_lazy() {
+    let source = "
+var big = range(0, 9223372036854775807);
+print len(big);
+print big[1000000000000];
+for (var i in big) { print i; break; }
+print range(10, 0, -3);
+";
+    assert_eq!(
+        printed(source),
+        [
+            "9223372036854775807",
+            "1000000000000",
+            "0",
+            "range(10, 0, -3)"
+        ]
+    );
+}
+
+#[test]
+fn print_visitor_renders_for_in_bodies() {
+    assert_eq!(
+        printed_tree("for (var x in [1, 2]) { print x; }"),
+        ["(for x (list 1 2) (block x))"]
+    );
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn map_literals_keep_insertion_order() {
+    let source = "
+var map = {\"a\": 1, 2: \"b\"};
+print map;
+print map[\"a\"];
+print map[2];
+print map[\"missing\"];
+map[\"c\"] = [1];
+map[\"a\"] = 3;
+print map;
+";
+    assert_eq!(
+        printed(source),
+        ["{a: 1, 2: b}", "1", "b", "nil", "{a: 3, 2: b, c: [1]}"]
+    );
+}
+
+#[test]
+fn map_natives_and_iteration_follow_the_keys() {
+    let source = "
+var map = {\"a\": 1, \"b\": 2, \"c\": 3};
+print keys(map);
+print values(map);
+print has(map, \"b\");
+print remove(map, \"b\");
+print has(map, \"b\");
+print len(map);
+for (var key in map) print key;
+";
+    assert_eq!(
+        printed(source),
+        [
+            "[a, b, c]",
+            "[1, 2, 3]",
+            "true",
+            "2",
+            "false",
+            "2",
+            "a",
+            "c"
+        ]
+    );
+}
+
+#[test]
+fn bad_keys_are_runtime_errors() {
+    assert_eq!(
+        error("var map = {};\nprint map[[1]];"),
+        "[line 2] Error: Map keys must be strings, numbers, booleans or nil."
+    );
+    assert_eq!(
+        error("var map = {[1]: 2};"),
+        "[line 1] Error: Map keys must be strings, numbers, booleans or nil."
+    );
+    assert_eq!(
+        error("has([], 1);"),
+        "[line 1] Error: has() expects a map as its first argument."
+    );
+}
+
+#[test]
+fn printing_a_cyclic_map_terminates() {
+    let source = "
+var map = {\"a\": 1};
+map[\"self\"] = map;
+print map;
+";
+    assert_eq!(printed(source), ["{a: 1, self: {...}}"]);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn arithmetic_natives_keep_integers_when_they_can() {
+    let source = "
+print sqrt(16);
+print pow(2, 10);
+print pow(2, 0.5);
+print abs(-3);
+print abs(-2.5);
+print floor(2.7);
+print ceil(2.1);
+print round(2.5);
+print round(-2.5);
+print min(3, 1, 2);
+print max(3, 1.5, 2);
+";
+    assert_eq!(
+        printed(source),
+        [
+            "4.0",
+            "1024",
+            "1.4142135623730951",
+            "3",
+            "2.5",
+            "2",
+            "3",
+            "3",
+            "-3",
+            "1",
+            "3"
+        ]
+    );
+}
+
+#[test]
+fn transcendental_natives_and_special_values() {
+    let source = "
+print sin(0);
+print cos(0);
+print atan2(1, 1) * 4 == pi;
+print log(exp(2));
+print log(8, 2);
+print isNan(nan);
+print isNan(1);
+print isInfinite(-inf);
+print floor(inf);
+print floor(nan);
+";
+    assert_eq!(
+        printed(source),
+        ["0.0", "1.0", "true", "2.0", "3.0", "true", "false", "true", "inf", "NaN"]
+    );
+}
+
+#[test]
+fn bad_arguments_are_runtime_errors() {
+    assert_eq!(
+        error("sqrt(\"x\");"),
+        "[line 1] Error: sqrt() expects a number as its first argument."
+    );
+    assert_eq!(
+        error("min();"),
+        "[line 1] Error: min() expected at least 1 arguments but got 0."
+    );
+    assert_eq!(
+        error("abs(-9223372036854775807 - 1);"),
+        "[line 1] Error: Integer overflow."
+    );
+    assert_eq!(
+        error("randomInt(3, 1);"),
+        "[line 1] Error: randomInt() lower bound must not be greater than upper bound."
+    );
+    assert_eq!(
+        error("seed(1.5);"),
+        "[line 1] Error: seed() expects an integer as its first argument."
+    );
+}
+
+#[test]
+fn seeding_makes_random_numbers_repeatable() {
+    let source = "
+var draw = fun () {
+    var numbers = [];
+    for (var i in range(0, 5)) {
+        push(numbers, random());
+        push(numbers, randomInt(-1000, 1000));
+    }
+    return numbers;
+};
+seed(42);
+var first = draw();
+seed(42);
+print first;
+print draw();
+seed(7);
+print draw();
+";
+    let lines = printed(source);
+    assert_eq!(lines[0], lines[1]);
+    assert_ne!(lines[0], lines[2]);
+    assert_eq!(printed(source), lines);
+}
+
+#[test]
+fn random_numbers_stay_in_range() {
+    let source = "
+seed(1);
+var low = 0.5;
+var high = 0.5;
+var smallest = 0;
+var largest = 0;
+for (var i in range(0, 1000)) {
+    var r = random();
+    low = min(low, r);
+    high = max(high, r);
+    var n = randomInt(-2, 2);
+    smallest = min(smallest, n);
+    largest = max(largest, n);
+}
+print low >= 0;
+print high < 1;
+print smallest;
+print largest;
+print randomInt(5, 5);
+";
+    assert_eq!(printed(source), ["true", "true", "-2", "2", "5"]);
+}
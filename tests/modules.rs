@@ -0,0 +1,127 @@
+mod common;
+
+use common::{interpret, run_files};
+use lox_interpreter::interpreter::{module::MemoryModuleLoader, Interpreter};
+
+const LIBRARY: &str = "export var shared = 1;\nvar hidden = 2;\nexport const K = 3;\n";
+
+#[test]
+fn only_exported_names_are_visible() {
+    let output = run_files(&[
+        (
+            "main.lox",
+            "import \"lib.lox\" as lib;\nfrom \"lib.lox\" import shared, K;\nprint lib.shared;\nprint shared + K;\nprint lib.hidden;\n",
+        ),
+        ("lib.lox", LIBRARY),
+    ]);
+    assert_eq!(output.stdout, "1\n4\n");
+    assert_eq!(
+        output.stderr,
+        "[line 5] Error: Module \"lib.lox\" has no export \"hidden\".\n"
+    );
+}
+
+#[test]
+fn importing_an_unexported_name_fails() {
+    let output = run_files(&[
+        ("main.lox", "from \"lib.lox\" import hidden;\n"),
+        ("lib.lox", LIBRARY),
+    ]);
+    assert_eq!(output.code, 70);
+    assert_eq!(
+        output.stderr,
+        "[line 1] Error: Module \"lib.lox\" has no export \"hidden\".\n"
+    );
+}
+
+#[test]
+fn a_module_is_executed_once() {
+    let output = run_files(&[
+        (
+            "main.lox",
+            "import \"lib.lox\" as a;\nimport \"lib.lox\" as b;\nprint a == b;\n",
+        ),
+        ("lib.lox", "print \"loading\";\n"),
+    ]);
+    assert_eq!(output.stdout, "loading\ntrue\n");
+}
+
+#[test]
+fn cyclic_imports_are_rejected() {
+    let output = run_files(&[
+        ("x.lox", "import \"y.lox\" as y;\n"),
+        ("y.lox", "import \"x.lox\" as x;\n"),
+    ]);
+    assert_eq!(output.code, 70);
+    assert_eq!(
+        output.stderr,
+        "[line 1 in \"y.lox\"] Error: Cyclic import of module \"x.lox\".\n"
+    );
+}
+
+#[test]
+fn errors_inside_a_module_name_the_module() {
+    let output = run_files(&[
+        ("main.lox", "import \"a.lox\" as a;\n"),
+        ("a.lox", "import \"b.lox\" as b;\n"),
+        ("b.lox", "\nprint missing;\n"),
+    ]);
+    assert_eq!(
+        output.stderr,
+        "[line 2 in \"b.lox\"] Error: Undefined variable \"missing\".\n"
+    );
+}
+
+#[test]
+fn missing_modules_are_reported_at_the_import() {
+    let output = run_files(&[("main.lox", "\nimport \"nope.lox\" as n;\n")]);
+    assert!(
+        output
+            .stderr
+            .starts_with("[line 2] Error: Could not load module \"nope.lox\":"),
+        "{}",
+        output.stderr
+    );
+}
+
+#[test]
+fn separately_parsed_scripts_keep_their_own_imports() {
+    let mut loader = MemoryModuleLoader::default();
+    loader.insert("a.lox", "export var name = \"a\";");
+    loader.insert("b.lox", "export var name = \"b\";");
+    let mut interpreter = Interpreter::new();
+    interpreter.set_module_loader(loader);
+
+    let first = interpreter
+        .parse("import \"a.lox\" as m; var first = m.name;")
+        .unwrap();
+    let second = interpreter
+        .parse("import \"b.lox\" as n; var second = n.name;")
+        .unwrap();
+    assert_ne!(first.statements[0].node(), second.statements[0].node());
+
+    for program in [&first, &second, &first, &second] {
+        interpreter.interpret(program).unwrap();
+    }
+    interpret(
+        &mut interpreter,
+        "var check = first + second; var fail = check == \"ab\" ? nil : missing;",
+    )
+    .unwrap();
+}
+
+#[test]
+fn imported_constants_stay_constant() {
+    let output = run_files(&[
+        (
+            "main.lox",
+            "from \"lib.lox\" import shared, K;\nshared = 5;\nprint shared;\nK = 4;\n",
+        ),
+        ("lib.lox", LIBRARY),
+    ]);
+    assert_eq!(output.stdout, "5\n");
+    assert_eq!(
+        output.stderr,
+        "[line 4] Error: Cannot assign to constant \"K\".\n"
+    );
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn integers_stay_exact_and_mix_into_floats() {
+    assert_eq!(
+        printed("print 9007199254740993; print 1 + 2; print 1 + 2.0; print 100.0; print 1 == 1.0;"),
+        ["9007199254740993", "3", "3.0", "100.0", "true"]
+    );
+    assert_eq!(
+        error("print 9223372036854775807 + 1;"),
+        "[line 1] Error: Integer overflow."
+    );
+}
+
+#[test]
+fn literals_print_the_same_in_the_tree_and_at_runtime() {
+    assert_eq!(printed_tree("print 3; print 3.0;"), ["3", "3.0"]);
+    assert_eq!(printed("print 3; print 3.0;"), ["3", "3.0"]);
+}
+
+#[test]
+fn division_always_produces_a_float() {
+    assert_eq!(
+        printed("print 7 / 2; print -7 / 2; print 6 / 3; print 7.0 / 2; print 1 / 0;"),
+        ["3.5", "-3.5", "2.0", "3.5", "inf"]
+    );
+}
+
+#[test]
+fn integer_division_floors() {
+    assert_eq!(
+        printed("print 7 ~/ 2; print -7 ~/ 2; print 7.5 ~/ 2;"),
+        ["3", "-4", "3.0"]
+    );
+    assert_eq!(error("print 1 ~/ 0;"), "[line 1] Error: Division by zero.");
+}
+
+#[test]
+fn modulo_keeps_integers_and_floats_apart() {
+    assert_eq!(
+        printed("print 7 % 3; print -7 % 3; print 7.5 % 2;"),
+        ["1", "2", "1.5"]
+    );
+    assert_eq!(error("print 1 % 0;"), "[line 1] Error: Division by zero.");
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use common::{error, printed, printed_tree};
+
+#[test]
+fn exponent_is_right_associative_and_binds_tighter_than_unary_minus() {
+    assert_eq!(
+        printed("print 2 ** 3 ** 2; print -2 ** 2; print 2 * 3 ** 2; print 2 ** -1;"),
+        ["512", "-4", "18", "0.5"]
+    );
+    assert_eq!(printed_tree("-2 ** 2;"), ["(- (** 2 2))"]);
+}
+
+#[test]
+fn bitwise_operators_work_on_integers() {
+    assert_eq!(
+        printed("print 5 & 3; print 5 | 3; print 5 ^ 3; print ~5; print 1 << 4; print -16 >> 2;"),
+        ["1", "7", "6", "-6", "16", "-4"]
+    );
+    assert_eq!(
+        error("print 1.5 & 1;"),
+        "[line 1] Error: Operands must be integers."
+    );
+    assert_eq!(
+        error("print 1 << 64;"),
+        "[line 1] Error: Shift amount out of range."
+    );
+}
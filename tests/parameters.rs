@@ -0,0 +1,72 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn defaults_rest_and_named_arguments_fill_parameters() {
+    let source = "
+var f = fun (a, b = a * 2, ...rest) { return [a, b, rest]; };
+print f(1);
+print f(1, 5);
+print f(1, 5, 6, 7);
+print f(b: 3, a: 2);
+";
+    assert_eq!(
+        printed(source),
+        ["[1, 2, []]", "[1, 5, []]", "[1, 5, [6, 7]]", "[2, 3, []]"]
+    );
+}
+
+#[test]
+fn defaults_are_evaluated_on_each_call_that_needs_them() {
+    let source = "
+var calls = 0;
+var f = fun (x = fun () { calls += 1; return calls; }()) { return x; };
+print f();
+print f();
+print f(10);
+print calls;
+";
+    assert_eq!(printed(source), ["1", "2", "10", "2"]);
+}
+
+#[test]
+fn arity_errors_name_the_problem() {
+    let f = "var f = fun (a) {};\n";
+    assert_eq!(
+        error(&format!("{}f();", f)),
+        "[line 2] Error: f() missing argument for parameter \"a\"."
+    );
+    assert_eq!(
+        error(&format!("{}f(1, 2);", f)),
+        "[line 2] Error: f() expected at most 1 arguments but got 2."
+    );
+    assert_eq!(
+        error(&format!("{}f(b: 1);", f)),
+        "[line 2] Error: f() got an unexpected argument \"b\"."
+    );
+    assert_eq!(
+        error(&format!("{}f(1, a: 2);", f)),
+        "[line 2] Error: f() got multiple values for parameter \"a\"."
+    );
+}
+
+#[test]
+fn malformed_parameter_lists_are_parse_errors() {
+    assert_eq!(
+        error("var f = fun (a, a) {};"),
+        "[line 1] Error: Duplicate parameter \"a\"."
+    );
+    assert_eq!(
+        error("var f = fun (...rest, a) {};"),
+        "[line 1] Error: Rest parameter must be last."
+    );
+    assert_eq!(
+        error("f(a: 1, 2);"),
+        "[line 1] Error: Positional arguments must come before named arguments."
+    );
+    assert_eq!(
+        error("f(a: 1, a: 2);"),
+        "[line 1] Error: Duplicate named argument \"a\"."
+    );
+}
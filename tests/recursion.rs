@@ -0,0 +1,62 @@
+mod common;
+
+use std::thread;
+
+use common::{interpret, printed, run};
+use lox_interpreter::{error::LoxError, interpreter::Interpreter};
+
+// Each call here nests several blocks, so it takes far more native stack than a plain call
+const NESTED_RECURSION: &str = "
+var f = fun (n) { try { for (var i in [n]) { { return f(n + 1); } } } finally { } };
+f(0);
+";
+
+fn stack_overflow(configure: fn(&mut Interpreter)) -> String {
+    // A default std::thread stack, which is what most embedders will run on
+    let embedded = thread::spawn(move || {
+        let mut interpreter = Interpreter::new();
+        configure(&mut interpreter);
+        match interpret(&mut interpreter, NESTED_RECURSION) {
+            Err(LoxError::RuntimeError(err)) => err.message,
+            other => panic!("expected a stack overflow, got {:?}", other.err()),
+        }
+    });
+    embedded.join().unwrap()
+}
+
+#[test]
+fn deep_recursion_reports_a_stack_overflow() {
+    let output = run("var f = fun (n) { return f(n + 1); };\nf(0);\n");
+    assert_eq!(
+        output.stderr.lines().next(),
+        Some("[line 1] Error: Stack overflow.")
+    );
+
+    assert_eq!(
+        printed("var f = fun (n) { return n == 0 ? 0 : 1 + f(n - 1); };\nprint f(4000);\n"),
+        ["4000"]
+    );
+}
+
+#[test]
+fn default_call_depth_fits_an_ordinary_thread() {
+    assert_eq!(stack_overflow(|_| {}), "Stack overflow.");
+}
+
+#[test]
+fn the_stack_budget_holds_without_a_call_depth_limit() {
+    assert_eq!(
+        stack_overflow(|interpreter| interpreter.set_max_call_depth(usize::MAX)),
+        "Stack overflow."
+    );
+}
+
+#[test]
+fn large_stack_runner_allows_deep_recursion() {
+    let handle = Interpreter::spawn_with_large_stack(|interpreter| {
+        let source = "var f = fun (n) { return n == 0 ? 0 : 1 + f(n - 1); };\nf(4000);\n";
+        interpret(interpreter, source).is_ok()
+    })
+    .unwrap();
+    assert!(handle.join().unwrap());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{error, run};
+
+#[test]
+fn uncaught_errors_print_a_stack_trace() {
+    let output = run("var f = fun () { throw \"oops\"; };\nvar g = fun () { f(); };\ng();\n");
+    assert_eq!(
+        output.stderr,
+        "[line 1] Error: Uncaught oops\n    in f() called at line 2\n    in g() called at line 3\n"
+    );
+}
+
+#[test]
+fn frames_are_named_after_what_the_function_is_bound_to() {
+    let source = "
+var outer = fun (callback = fun () { throw \"oops\"; }) { callback(); };
+const run = fun (step) { step(); };
+run(step: fun () { outer(); });
+";
+    assert_eq!(
+        run(source).stderr,
+        "[line 2] Error: Uncaught oops
+    in callback() called at line 2
+    in outer() called at line 4
+    in step() called at line 3
+    in run() called at line 4
+"
+    );
+    assert_eq!(
+        run("[fun () { len(); }][0]();").stderr,
+        "[line 1] Error: len() expected 1 arguments but got 0.\n    in len() called at line 1\n    in lambda() called at line 1\n"
+    );
+}
+
+#[test]
+fn arity_errors_use_the_function_name() {
+    assert_eq!(
+        error("var f = fun (a) {};\nf();"),
+        "[line 2] Error: f() missing argument for parameter \"a\"."
+    );
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{error, printed};
+
+#[test]
+fn lengths_and_positions_count_characters() {
+    let source = "
+var s = \"héllo wörld 😀\";
+print len(s);
+print substring(s, 1, 4);
+print substring(s, 12);
+print indexOf(s, \"w\");
+print indexOf(s, \"z\");
+print chars(\"aé😀\");
+";
+    assert_eq!(
+        printed(source),
+        ["13", "éll", "😀", "6", "-1", "[a, é, 😀]"]
+    );
+}
+
+#[test]
+fn splitting_joining_and_case() {
+    let source = "
+print split(\"a,b,,c\", \",\");
+print split(\"hé\", \"\");
+print join([\"x\", \"y\"], \"-\");
+print trim(\"  hé  \");
+print upper(\"straße\");
+print lower(\"ÉCOLE\");
+print replace(\"aXbXc\", \"X\", \"é\");
+print startsWith(\"héllo\", \"hé\");
+print endsWith(\"ok 😀\", \"😀\");
+print repeat(\"é\", 3);
+";
+    assert_eq!(
+        printed(source),
+        [
+            "[a, b, , c]",
+            "[h, é]",
+            "x-y",
+            "hé",
+            "STRASSE",
+            "école",
+            "aébéc",
+            "true",
+            "true",
+            "ééé"
+        ]
+    );
+}
+
+#[test]
+fn bad_arguments_are_runtime_errors() {
+    assert_eq!(
+        error("substring(\"abc\", 2, 1);"),
+        "[line 1] Error: substring() start must not be greater than end."
+    );
+    assert_eq!(
+        error("substring(\"abc\", 0, 9);"),
+        "[line 1] Error: substring() index out of range."
+    );
+    assert_eq!(
+        error("upper(1);"),
+        "[line 1] Error: upper() expects a string as its first argument."
+    );
+    assert_eq!(
+        error("join([1], \",\");"),
+        "[line 1] Error: join() expects a list of strings."
+    );
+}
+
+#[test]
+fn huge_repeats_fail_cleanly() {
+    assert_eq!(
+        error("repeat(\"ab\", 9223372036854775807);"),
+        "[line 1] Error: repeat() result is too large."
+    );
+    assert_eq!(
+        error("repeat(\"a\", 2000000000);"),
+        "[line 1] Error: repeat() result is too large."
+    );
+}